@@ -1,20 +1,81 @@
 use std::env;
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
 
+pub mod automaton;
+mod bit_grid;
+pub mod cycle;
 mod direction;
 mod field;
+pub mod geometry;
+pub mod graph;
+pub mod image;
+pub mod input;
+pub mod interval;
+pub mod math;
+pub mod memo;
+pub mod nonogram;
+pub mod operators;
+pub mod parse;
 mod point;
+mod region;
+pub mod search;
+pub mod select;
+pub mod testing;
+pub mod trace;
+pub mod viz;
+pub mod vm;
 
+pub use bit_grid::{BitGrid, DirectionGrid};
 pub use direction::Direction;
-pub use field::Field;
+pub use field::{Axis, Field, Reflection};
 pub use point::Point;
+pub use region::Region;
 
+// A single part of a puzzle. The runner reads the input file, solvers get
+// the text, so they can be fed with the examples in tests
 pub trait Puzzle {
-    fn solve(file_name: &str) -> String;
+    fn solve(input: &str) -> String;
     fn year() -> i32;
     fn day() -> i32;
     fn part() -> i32;
 }
 
+// A whole day of a puzzle. The input is parsed once and both parts are solved
+// from the parsed data, see solve_day. Use DayPart to run a single part where
+// a Puzzle is expected
+pub trait Day {
+    type Parsed;
+    fn year() -> i32;
+    fn day() -> i32;
+    fn parse(input: &str) -> Self::Parsed;
+    fn part1(parsed: &Self::Parsed) -> String;
+    fn part2(parsed: &Self::Parsed) -> String;
+}
+
+// Puzzle adapter for a single part of a Day, parses the input on its own
+pub struct DayPart<D, const PART: i32> {
+    day: PhantomData<D>,
+}
+
+impl<D: Day, const PART: i32> Puzzle for DayPart<D, PART> {
+    fn solve(input: &str) -> String {
+        solve_day_part::<D>(&D::parse(input), PART)
+    }
+
+    fn year() -> i32 {
+        D::year()
+    }
+
+    fn day() -> i32 {
+        D::day()
+    }
+
+    fn part() -> i32 {
+        PART
+    }
+}
+
 pub trait Solver {
     fn solve(year: i32, day: i32, part: i32);
 }
@@ -55,6 +116,24 @@ pub fn get_input_file_names(day: i32, year: i32) -> Vec<String> {
     entities.map(|e| e.unwrap().display().to_string()).collect()
 }
 
+// Runs the closure with the trace context of the given puzzle part and measures how long it takes
+fn run_timed<R>(context: trace::Context, f: impl FnOnce() -> R) -> (R, Duration) {
+    trace::set_context(Some(context));
+    let now = Instant::now();
+    let result = f();
+    let elapsed = now.elapsed();
+    trace::set_context(None);
+    (result, elapsed)
+}
+
+fn input_file_names_or_panic(day: i32, year: i32) -> Vec<String> {
+    let file_names = get_input_file_names(day, year);
+    if file_names.is_empty() {
+        panic!("No input files found for year {} day {}", year, day);
+    }
+    file_names
+}
+
 pub fn solve<Puzzle: crate::Puzzle>(day: i32, part: i32) {
     if !day_matched(day, Puzzle::day()) {
         return;
@@ -63,16 +142,15 @@ pub fn solve<Puzzle: crate::Puzzle>(day: i32, part: i32) {
         return;
     }
 
-    let file_names = get_input_file_names(Puzzle::day(), Puzzle::year());
-    if file_names.is_empty() {
-        panic!("No input files found for year {} day {}", Puzzle::year(), Puzzle::day());
-    }
-    for f in file_names.iter() {
-        use std::time::Instant;
-
-        let now = Instant::now();
-        let answer = Puzzle::solve(&f);
-        let elapsed = now.elapsed();
+    for f in input_file_names_or_panic(Puzzle::day(), Puzzle::year()).iter() {
+        let text = input::read(f);
+        let context = trace::Context {
+            year: Puzzle::year(),
+            day: Puzzle::day(),
+            part: Puzzle::part(),
+            input: f.clone(),
+        };
+        let (answer, elapsed) = run_timed(context, || Puzzle::solve(&text));
 
         println!(
             "{} Day {:0>2}, part {}: {}\n\tElapsed: {:.2?}\n",
@@ -84,3 +162,94 @@ pub fn solve<Puzzle: crate::Puzzle>(day: i32, part: i32) {
         );
     }
 }
+
+// Same as solve, but the input of every file is parsed only once for both parts.
+// Parsing and each part are timed separately
+pub fn solve_day<D: Day>(day: i32, part: i32) {
+    if !day_matched(day, D::day()) {
+        return;
+    }
+    let parts: Vec<i32> = [1, 2].into_iter().filter(|&p| part_matched(part, p)).collect();
+    if parts.is_empty() {
+        return;
+    }
+
+    for f in input_file_names_or_panic(D::day(), D::year()).iter() {
+        let context = |part| trace::Context {
+            year: D::year(),
+            day: D::day(),
+            part,
+            input: f.clone(),
+        };
+
+        // part 0 in traces stands for parsing
+        let (parsed, elapsed) = run_timed(context(0), || D::parse(&input::read(f)));
+        println!(
+            "{} Day {:0>2}, parsed\n\tElapsed: {:.2?}\n",
+            D::year(),
+            D::day(),
+            elapsed
+        );
+
+        for &p in parts.iter() {
+            let (answer, elapsed) = run_timed(context(p), || solve_day_part::<D>(&parsed, p));
+            println!(
+                "{} Day {:0>2}, part {}: {}\n\tElapsed: {:.2?}\n",
+                D::year(),
+                D::day(),
+                p,
+                answer,
+                elapsed
+            );
+        }
+    }
+}
+
+fn solve_day_part<D: Day>(parsed: &D::Parsed, part: i32) -> String {
+    match part {
+        1 => D::part1(parsed),
+        2 => D::part2(parsed),
+        _ => panic!("Unexpected part {}", part),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Day, DayPart, Puzzle};
+
+    // sums the numbers in part 1 and multiplies them in part 2
+    struct Numbers {}
+    impl Day for Numbers {
+        type Parsed = Vec<u64>;
+
+        fn parse(input: &str) -> Vec<u64> {
+            input.split_whitespace().map(|n| n.parse().unwrap()).collect()
+        }
+
+        fn part1(numbers: &Vec<u64>) -> String {
+            numbers.iter().sum::<u64>().to_string()
+        }
+
+        fn part2(numbers: &Vec<u64>) -> String {
+            numbers.iter().product::<u64>().to_string()
+        }
+
+        fn year() -> i32 {
+            2023
+        }
+
+        fn day() -> i32 {
+            5
+        }
+    }
+
+    #[test]
+    fn day_part_test() {
+        type Part1 = DayPart<Numbers, 1>;
+        type Part2 = DayPart<Numbers, 2>;
+        assert_eq!((Part1::year(), Part1::day(), Part1::part()), (2023, 5, 1));
+        assert_eq!((Part2::year(), Part2::day(), Part2::part()), (2023, 5, 2));
+        assert_eq!(Part1::solve("2 3 4"), "9");
+        assert_eq!(Part2::solve("2 3 4"), "24");
+    }
+}
@@ -0,0 +1,315 @@
+// Cellular automata over a Field: every generation each cell gets a new value
+// computed by a rule from the cell and its neighbours, all cells at once.
+//
+// let mut automaton = Automaton::new(field, Neighborhood::All, |node, neighbors| ...);
+// automaton.run_until_stable();
+//
+// The field is double buffered, so a generation doesn't clone it. with_frontier()
+// makes every generation evaluate only the cells next to the ones changed by
+// the previous generation, which is a big win when the activity dies out.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::cycle::Cycle;
+use crate::point::PointNeighborIterator;
+use crate::{BitGrid, Field, Point};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighborhood {
+    // 4 neighbours (von Neumann)
+    Orthogonal,
+    // 8 neighbours (Moore)
+    All,
+}
+
+impl Neighborhood {
+    fn of<'a>(&self, point: &'a Point<i32>) -> PointNeighborIterator<'a, i32> {
+        match self {
+            Neighborhood::Orthogonal => point.neighbors_orthogonal(),
+            Neighborhood::All => point.neighbors_all(),
+        }
+    }
+}
+
+// Neighbours of a cell that are inside of the field
+pub struct Neighbors<'a, Node> {
+    nodes: [Option<&'a Node>; 8],
+}
+
+impl<'a, Node> Neighbors<'a, Node>
+where
+    Node: Default + Clone,
+{
+    fn new(field: &'a Field<Node>, point: &Point<i32>, neighborhood: Neighborhood) -> Self {
+        let mut nodes = [None; 8];
+        for (slot, n) in nodes.iter_mut().zip(neighborhood.of(point)) {
+            if field.is_inside(n.row, n.col) {
+                *slot = Some(field.get(n.row as usize, n.col as usize));
+            }
+        }
+        Neighbors { nodes }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a Node> + '_ {
+        self.nodes.iter().flatten().copied()
+    }
+
+    // Number of neighbours matching the predicate
+    pub fn count<F>(&self, mut predicate: F) -> usize
+    where
+        F: FnMut(&Node) -> bool,
+    {
+        self.iter().filter(|n| predicate(n)).count()
+    }
+}
+
+// Rule without captured state, handy for naming the automaton type
+pub type Rule<Node> = fn(&Node, &Neighbors<Node>) -> Node;
+
+pub struct Automaton<Node, R> {
+    current: Field<Node>,
+    // always equal to current between generations
+    next: Field<Node>,
+    neighborhood: Neighborhood,
+    rule: R,
+    track_frontier: bool,
+    // cells to evaluate in the next generation, None for all of them
+    frontier: Option<BitGrid>,
+    generation: usize,
+}
+
+impl<Node, R> Automaton<Node, R>
+where
+    Node: Default + Clone + PartialEq,
+    R: FnMut(&Node, &Neighbors<Node>) -> Node,
+{
+    pub fn new(field: Field<Node>, neighborhood: Neighborhood, rule: R) -> Self {
+        Automaton {
+            next: field.clone(),
+            current: field,
+            neighborhood,
+            rule,
+            track_frontier: false,
+            frontier: None,
+            generation: 0,
+        }
+    }
+
+    // Only the cells changed by the previous generation and their neighbours are
+    // evaluated. The rule must depend on the cell and its neighbours only
+    pub fn with_frontier(self) -> Self {
+        Automaton {
+            track_frontier: true,
+            ..self
+        }
+    }
+
+    pub fn field(&self) -> &Field<Node> {
+        &self.current
+    }
+
+    pub fn into_field(self) -> Field<Node> {
+        self.current
+    }
+
+    // Number of generations computed so far
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    // Computes the next generation, returns the number of changed cells
+    pub fn step(&mut self) -> usize {
+        let mut changed = Vec::new();
+        let frontier = self.frontier.take();
+        let points: Box<dyn Iterator<Item = Point<i32>>> = match frontier.as_ref() {
+            Some(frontier) => Box::new(frontier.iter()),
+            None => {
+                let col_count = self.current.get_col_count();
+                let count = self.current.get_row_count() * col_count;
+                Box::new((0..count).map(move |i| Point {
+                    row: (i / col_count) as i32,
+                    col: (i % col_count) as i32,
+                }))
+            }
+        };
+        for point in points {
+            let (row, col) = (point.row as usize, point.col as usize);
+            let node = self.current.get(row, col);
+            let neighbors = Neighbors::new(&self.current, &point, self.neighborhood);
+            let new_node = (self.rule)(node, &neighbors);
+            if new_node != *node {
+                *self.next.get_mut(row, col) = new_node;
+                changed.push(point);
+            }
+        }
+
+        std::mem::swap(&mut self.current, &mut self.next);
+        for point in changed.iter() {
+            let (row, col) = (point.row as usize, point.col as usize);
+            *self.next.get_mut(row, col) = self.current.get(row, col).clone();
+        }
+        if self.track_frontier {
+            self.frontier = Some(self.frontier_of(&changed));
+        }
+        self.generation += 1;
+        changed.len()
+    }
+
+    fn frontier_of(&self, changed: &[Point<i32>]) -> BitGrid {
+        let mut result = BitGrid::with_size(self.current.get_row_count(), self.current.get_col_count());
+        for point in changed {
+            result.set(point.row as usize, point.col as usize);
+            for n in self.neighborhood.of(point) {
+                if result.is_inside(n.row, n.col) {
+                    result.set(n.row as usize, n.col as usize);
+                }
+            }
+        }
+        result
+    }
+
+    // Runs the given number of generations. Stops early once nothing changes,
+    // the generation counter still advances by the full amount
+    pub fn run(&mut self, steps: usize) {
+        let target = self.generation + steps;
+        while self.generation < target {
+            if self.step() == 0 {
+                self.generation = target;
+            }
+        }
+    }
+
+    // Runs until a generation doesn't change anything. Returns the number of
+    // generations that did change something
+    pub fn run_until_stable(&mut self) -> usize {
+        let mut result = 0;
+        while self.step() != 0 {
+            result += 1;
+        }
+        result
+    }
+
+    // Same as run() but remembers every generation and jumps ahead once one
+    // repeats, so the number of steps can be arbitrary large. Returns the cycle
+    // in generations counted from the call, if there was one
+    pub fn run_detecting_cycle(&mut self, steps: usize) -> Option<Cycle>
+    where
+        Node: Eq + Hash,
+    {
+        let mut seen: HashMap<Field<Node>, usize> = HashMap::new();
+        let mut history = Vec::new();
+        for i in 0..steps {
+            if let Some(&start) = seen.get(&self.current) {
+                let cycle = Cycle {
+                    start,
+                    length: i - start,
+                };
+                self.current = history.swap_remove(cycle.wrap_index(steps));
+                self.next = self.current.clone();
+                self.frontier = None;
+                self.generation += steps - i;
+                return Some(cycle);
+            }
+            seen.insert(self.current.clone(), i);
+            history.push(self.current.clone());
+            self.step();
+        }
+        None
+    }
+}
+
+// One generation of a two-state automaton on an unbounded grid given by its
+// live cells. The rule gets whether the cell is alive and the number of live
+// neighbours. Only live cells and their neighbours are evaluated, so a cell
+// with no live neighbours can't come alive
+pub fn step_sparse<F>(live: &HashSet<Point<i32>>, neighborhood: Neighborhood, mut rule: F) -> HashSet<Point<i32>>
+where
+    F: FnMut(bool, usize) -> bool,
+{
+    let mut counts: HashMap<Point<i32>, usize> = live.iter().map(|p| (*p, 0)).collect();
+    for point in live {
+        for n in neighborhood.of(point) {
+            *counts.entry(n).or_default() += 1;
+        }
+    }
+    counts
+        .into_iter()
+        .filter(|(point, count)| rule(live.contains(point), *count))
+        .map(|(point, _)| point)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{step_sparse, Automaton, Neighborhood};
+    use crate::cycle::Cycle;
+    use crate::{Field, Point};
+
+    fn parse(rows: &[&str]) -> Field<bool> {
+        let nodes = rows.iter().flat_map(|row| row.chars()).map(|c| c == '#').collect();
+        Field::from_flat_vector(nodes, rows[0].len()).unwrap()
+    }
+
+    fn life(alive: &bool, neighbors: &super::Neighbors<bool>) -> bool {
+        let count = neighbors.count(|n| *n);
+        count == 3 || (*alive && count == 2)
+    }
+
+    #[test]
+    fn blinker_test() {
+        let vertical = parse(&[".....", "..#..", "..#..", "..#..", "....."]);
+        let horizontal = parse(&[".....", ".....", ".###.", ".....", "....."]);
+
+        let mut automaton = Automaton::new(vertical.clone(), Neighborhood::All, life);
+        assert_eq!(automaton.step(), 4);
+        assert_eq!(*automaton.field(), horizontal);
+        automaton.run(3);
+        assert_eq!(automaton.generation(), 4);
+        assert_eq!(*automaton.field(), vertical);
+
+        let cycle = automaton.run_detecting_cycle(1_000_001);
+        assert_eq!(cycle, Some(Cycle { start: 0, length: 2 }));
+        assert_eq!(automaton.generation(), 1_000_005);
+        assert_eq!(*automaton.field(), horizontal);
+    }
+
+    #[test]
+    fn frontier_test() {
+        // erosion: a cell survives only if all of its orthogonal neighbours are set
+        let erode = |node: &bool, neighbors: &super::Neighbors<bool>| *node && neighbors.count(|n| *n) == 4;
+        let field = parse(&["#######", "#######", "#######", "#######", "#######"]);
+
+        let mut full = Automaton::new(field.clone(), Neighborhood::Orthogonal, erode);
+        let mut frontier = Automaton::new(field, Neighborhood::Orthogonal, erode).with_frontier();
+        assert_eq!(full.run_until_stable(), 3);
+        assert_eq!(frontier.run_until_stable(), 3);
+        assert_eq!(full.field(), frontier.field());
+        assert_eq!(frontier.generation(), 4);
+        assert_eq!(*frontier.field(), Field::with_size(5, 7));
+    }
+
+    #[test]
+    fn step_sparse_test() {
+        let glider: HashSet<Point<i32>> = [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]
+            .into_iter()
+            .map(|(row, col)| Point { row, col })
+            .collect();
+        let mut live = glider.clone();
+        for _ in 0..4 {
+            live = step_sparse(&live, Neighborhood::All, |alive, count| {
+                count == 3 || (alive && count == 2)
+            });
+        }
+        let moved: HashSet<Point<i32>> = glider
+            .iter()
+            .map(|p| Point {
+                row: p.row + 1,
+                col: p.col + 1,
+            })
+            .collect();
+        assert_eq!(live, moved);
+    }
+}
@@ -0,0 +1,305 @@
+use crate::{Direction, Point};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+fn word_count(bit_count: usize) -> usize {
+    bit_count.div_ceil(WORD_BITS)
+}
+
+// A dense set of grid points, one bit per node. Shaped like Field,
+// so it's a cheap replacement for Field<bool> in visited sets
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    row_count: usize,
+    col_count: usize,
+}
+
+impl BitGrid {
+    pub fn with_size(row_count: usize, col_count: usize) -> Self {
+        BitGrid {
+            words: vec![0; word_count(row_count * col_count)],
+            row_count,
+            col_count,
+        }
+    }
+
+    pub fn get_row_count(&self) -> usize {
+        self.row_count
+    }
+
+    pub fn get_col_count(&self) -> usize {
+        self.col_count
+    }
+
+    pub fn is_inside(&self, row: i32, col: i32) -> bool {
+        row >= 0 && col >= 0 && (row as usize) < self.row_count && (col as usize) < self.col_count
+    }
+
+    fn bit_index(&self, row: usize, col: usize) -> (usize, u64) {
+        assert!(
+            row < self.row_count && col < self.col_count,
+            "({}, {}) is out of {}x{} grid",
+            row,
+            col,
+            self.row_count,
+            self.col_count
+        );
+        let index = row * self.col_count + col;
+        (index / WORD_BITS, 1 << (index % WORD_BITS))
+    }
+
+    // Returns true if the point wasn't set before
+    pub fn set(&mut self, row: usize, col: usize) -> bool {
+        let (word, mask) = self.bit_index(row, col);
+        let was_set = self.words[word] & mask != 0;
+        self.words[word] |= mask;
+        !was_set
+    }
+
+    pub fn reset(&mut self, row: usize, col: usize) {
+        let (word, mask) = self.bit_index(row, col);
+        self.words[word] &= !mask;
+    }
+
+    pub fn test(&self, row: usize, col: usize) -> bool {
+        let (word, mask) = self.bit_index(row, col);
+        self.words[word] & mask != 0
+    }
+
+    // Number of set points
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    pub fn union_with(&mut self, other: &BitGrid) {
+        self.check_same_size(other);
+        for (a, b) in self.words.iter_mut().zip(other.words.iter()) {
+            *a |= b;
+        }
+    }
+
+    pub fn intersect_with(&mut self, other: &BitGrid) {
+        self.check_same_size(other);
+        for (a, b) in self.words.iter_mut().zip(other.words.iter()) {
+            *a &= b;
+        }
+    }
+
+    pub fn union(&self, other: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result.union_with(other);
+        result
+    }
+
+    pub fn intersection(&self, other: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result.intersect_with(other);
+        result
+    }
+
+    fn check_same_size(&self, other: &BitGrid) {
+        assert_eq!(
+            (self.row_count, self.col_count),
+            (other.row_count, other.col_count),
+            "grids have different sizes"
+        );
+    }
+
+    // Set points in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = Point<i32>> + '_ {
+        self.words.iter().enumerate().flat_map(move |(word_idx, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                let index = word_idx * WORD_BITS + bit;
+                Some(Point {
+                    row: (index / self.col_count) as i32,
+                    col: (index % self.col_count) as i32,
+                })
+            })
+        })
+    }
+}
+
+// Four bits per node, one for every Direction flag. Handy for "already been
+// here going this way" checks in beam and guard walks
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct DirectionGrid {
+    words: Vec<u64>,
+    row_count: usize,
+    col_count: usize,
+}
+
+const NODE_BITS: usize = 4;
+const NODES_PER_WORD: usize = WORD_BITS / NODE_BITS;
+
+impl DirectionGrid {
+    pub fn with_size(row_count: usize, col_count: usize) -> Self {
+        DirectionGrid {
+            words: vec![0; word_count(row_count * col_count * NODE_BITS)],
+            row_count,
+            col_count,
+        }
+    }
+
+    pub fn get_row_count(&self) -> usize {
+        self.row_count
+    }
+
+    pub fn get_col_count(&self) -> usize {
+        self.col_count
+    }
+
+    pub fn is_inside(&self, row: i32, col: i32) -> bool {
+        row >= 0 && col >= 0 && (row as usize) < self.row_count && (col as usize) < self.col_count
+    }
+
+    fn node_index(&self, row: usize, col: usize) -> (usize, u32) {
+        assert!(
+            row < self.row_count && col < self.col_count,
+            "({}, {}) is out of {}x{} grid",
+            row,
+            col,
+            self.row_count,
+            self.col_count
+        );
+        let index = row * self.col_count + col;
+        (index / NODES_PER_WORD, ((index % NODES_PER_WORD) * NODE_BITS) as u32)
+    }
+
+    // Returns true if the node wasn't visited in this direction before
+    pub fn set(&mut self, row: usize, col: usize, direction: Direction) -> bool {
+        let (word, shift) = self.node_index(row, col);
+        let mask = (direction as u64) << shift;
+        let was_set = self.words[word] & mask != 0;
+        self.words[word] |= mask;
+        !was_set
+    }
+
+    pub fn test(&self, row: usize, col: usize, direction: Direction) -> bool {
+        self.get(row, col) & direction as u8 != 0
+    }
+
+    // All the direction flags of the node
+    pub fn get(&self, row: usize, col: usize) -> u8 {
+        let (word, shift) = self.node_index(row, col);
+        ((self.words[word] >> shift) & 0xF) as u8
+    }
+
+    // Number of nodes visited in any direction
+    pub fn count(&self) -> usize {
+        // fold every nibble into its lowest bit
+        const LOW_BITS: u64 = 0x1111_1111_1111_1111;
+        self.words
+            .iter()
+            .map(|&w| ((w | w >> 1 | w >> 2 | w >> 3) & LOW_BITS).count_ones() as usize)
+            .sum()
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    // Nodes visited in any direction
+    pub fn to_bit_grid(&self) -> BitGrid {
+        let mut result = BitGrid::with_size(self.row_count, self.col_count);
+        for row in 0..self.row_count {
+            for col in 0..self.col_count {
+                if self.get(row, col) != 0 {
+                    result.set(row, col);
+                }
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BitGrid, DirectionGrid};
+    use crate::{Direction, Point};
+
+    #[test]
+    fn bit_grid_test() {
+        let mut grid = BitGrid::with_size(9, 10);
+        assert_eq!(grid.count(), 0);
+        assert!(grid.set(0, 0));
+        assert!(grid.set(6, 4));
+        assert!(grid.set(8, 9));
+        assert!(!grid.set(6, 4));
+        assert_eq!(grid.count(), 3);
+        assert!(grid.test(6, 4));
+        assert!(!grid.test(4, 6));
+
+        assert_eq!(
+            grid.iter().collect::<Vec<_>>(),
+            vec![
+                Point { row: 0, col: 0 },
+                Point { row: 6, col: 4 },
+                Point { row: 8, col: 9 }
+            ]
+        );
+
+        grid.reset(6, 4);
+        assert!(!grid.test(6, 4));
+        assert_eq!(grid.count(), 2);
+
+        assert!(grid.is_inside(8, 9));
+        assert!(!grid.is_inside(9, 0));
+        assert!(!grid.is_inside(0, -1));
+
+        grid.clear();
+        assert_eq!(grid.iter().count(), 0);
+    }
+
+    #[test]
+    fn set_operations_test() {
+        let mut a = BitGrid::with_size(3, 3);
+        let mut b = BitGrid::with_size(3, 3);
+        for i in 0..3 {
+            a.set(i, i);
+            b.set(i, 2 - i);
+        }
+
+        let union = a.union(&b);
+        assert_eq!(union.count(), 5);
+        let intersection = a.intersection(&b);
+        assert_eq!(intersection.iter().collect::<Vec<_>>(), vec![Point { row: 1, col: 1 }]);
+    }
+
+    #[test]
+    fn direction_grid_test() {
+        let mut grid = DirectionGrid::with_size(5, 7);
+        assert!(grid.set(0, 0, Direction::East));
+        assert!(grid.set(0, 0, Direction::South));
+        assert!(!grid.set(0, 0, Direction::East));
+        assert!(grid.set(4, 6, Direction::North));
+        assert!(grid.set(2, 3, Direction::West));
+
+        assert_eq!(grid.get(0, 0), 0xC);
+        assert!(grid.test(0, 0, Direction::South));
+        assert!(!grid.test(0, 0, Direction::North));
+        assert_eq!(grid.get(4, 6), 0x1);
+        assert_eq!(grid.get(2, 2), 0);
+        assert_eq!(grid.count(), 3);
+
+        let visited = grid.to_bit_grid();
+        assert_eq!(
+            visited.iter().collect::<Vec<_>>(),
+            vec![
+                Point { row: 0, col: 0 },
+                Point { row: 2, col: 3 },
+                Point { row: 4, col: 6 }
+            ]
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// A sequence x0, x1 = f(x0), x2 = f(x1), ... produced by a function over a finite
+// set of states always ends up in a cycle: x(start + length) == x(start)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    // Index of the step within the first cycle occurrence that has the same state as step n
+    pub fn wrap_index(&self, n: usize) -> usize {
+        if n < self.start {
+            return n;
+        }
+        self.start + (n - self.start) % self.length
+    }
+}
+
+// Floyd's tortoise and hare. Only keeps a couple of states in memory,
+// but calls the step function about three times per sequence element
+pub fn floyd<S, F>(initial: S, mut step: F) -> Cycle
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let next = step(&hare);
+        hare = step(&next);
+    }
+
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+// Brent's algorithm. Same memory footprint as Floyd's one but fewer step calls
+pub fn brent<S, F>(initial: S, mut step: F) -> Cycle
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    let mut start = 0;
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+// Remembers every visited state, so each step is computed only once.
+// Returns the cycle along with all the states up to the cycle end
+pub fn detect_cycle<S, F>(initial: S, mut step: F) -> (Cycle, Vec<S>)
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut history = Vec::new();
+    let mut state = initial;
+    loop {
+        if let Some(&start) = seen.get(&state) {
+            let cycle = Cycle {
+                start,
+                length: history.len() - start,
+            };
+            return (cycle, history);
+        }
+        let next = step(&state);
+        seen.insert(state.clone(), history.len());
+        history.push(state);
+        state = next;
+    }
+}
+
+// State after applying the step function n times to the initial state. Jumps
+// ahead as soon as a cycle is detected, so n can be arbitrary large
+pub fn state_after_n_steps<S, F>(initial: S, mut step: F, n: usize) -> S
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut history = Vec::new();
+    let mut state = initial;
+    for i in 0..n {
+        if let Some(&start) = seen.get(&state) {
+            let cycle = Cycle {
+                start,
+                length: i - start,
+            };
+            return history.swap_remove(cycle.wrap_index(n));
+        }
+        let next = step(&state);
+        seen.insert(state.clone(), i);
+        history.push(state);
+        state = next;
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{brent, detect_cycle, floyd, state_after_n_steps, Cycle};
+
+    // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 6 -> 3 -> ...
+    fn step(x: &u32) -> u32 {
+        if *x == 6 {
+            3
+        } else {
+            x + 1
+        }
+    }
+
+    #[test]
+    fn floyd_test() {
+        assert_eq!(floyd(0, step), Cycle { start: 3, length: 4 });
+        assert_eq!(floyd(5, step), Cycle { start: 0, length: 4 });
+        assert_eq!(floyd(7, |_| 1), Cycle { start: 1, length: 1 });
+    }
+
+    #[test]
+    fn brent_test() {
+        assert_eq!(brent(0, step), Cycle { start: 3, length: 4 });
+        assert_eq!(brent(5, step), Cycle { start: 0, length: 4 });
+        assert_eq!(brent(7, |_| 1), Cycle { start: 1, length: 1 });
+    }
+
+    #[test]
+    fn detect_cycle_test() {
+        let (cycle, history) = detect_cycle(0, step);
+        assert_eq!(cycle, Cycle { start: 3, length: 4 });
+        assert_eq!(history, vec![0, 1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn wrap_index_test() {
+        let cycle = Cycle { start: 3, length: 4 };
+        assert_eq!(cycle.wrap_index(2), 2);
+        assert_eq!(cycle.wrap_index(3), 3);
+        assert_eq!(cycle.wrap_index(6), 6);
+        assert_eq!(cycle.wrap_index(7), 3);
+        assert_eq!(cycle.wrap_index(12), 4);
+    }
+
+    #[test]
+    fn state_after_n_steps_test() {
+        assert_eq!(state_after_n_steps(0, step, 0), 0);
+        assert_eq!(state_after_n_steps(0, step, 2), 2);
+        assert_eq!(state_after_n_steps(0, step, 7), 3);
+        assert_eq!(state_after_n_steps(0, step, 12), 4);
+        assert_eq!(state_after_n_steps(0, step, 1_000_000_000), 4);
+    }
+}
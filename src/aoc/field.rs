@@ -1,4 +1,27 @@
-#[derive(Debug, Default, Clone)]
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+
+use crate::{Point, Region};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Axis {
+    // a mirror line between two rows
+    Horizontal,
+    // a mirror line between two columns
+    Vertical,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Reflection {
+    pub axis: Axis,
+    // number of rows above a horizontal line or columns left of a vertical one
+    pub position: usize,
+    // nodes that differ from their mirror image, each pair counted once
+    pub mismatches: usize,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 pub struct Field<Node> {
     nodes: Vec<Node>,
     row_count: usize,
@@ -62,6 +85,103 @@ where
         self.try_get(row, col).expect("Index out of bounds")
     }
 
+    fn try_get_point(&self, point: &Point<i32>) -> Option<&Node> {
+        if !self.is_inside(point.row, point.col) {
+            return None;
+        }
+        self.try_get(point.row as usize, point.col as usize)
+    }
+
+    // Collects all nodes orthogonally reachable from the start node through
+    // the nodes matching the predicate. Returns None if the start node doesn't match
+    pub fn flood_fill<F>(&self, start: Point<i32>, mut predicate: F) -> Option<Region>
+    where
+        F: FnMut(&Node) -> bool,
+    {
+        if !predicate(self.try_get_point(&start)?) {
+            return None;
+        }
+
+        let mut visited = vec![false; self.nodes.len()];
+        let mut points = Vec::new();
+        let mut queue = VecDeque::new();
+
+        visited[self.flat_index(start.row as usize, start.col as usize)?] = true;
+        queue.push_back(start);
+
+        while let Some(point) = queue.pop_front() {
+            for n in point.neighbors_orthogonal() {
+                let Some(node) = self.try_get_point(&n) else {
+                    continue;
+                };
+                let idx = n.row as usize * self.col_count + n.col as usize;
+                if visited[idx] || !predicate(node) {
+                    continue;
+                }
+                visited[idx] = true;
+                queue.push_back(n);
+            }
+            points.push(point);
+        }
+
+        Some(Region::from_points(points, |p| {
+            self.is_inside(p.row, p.col) && visited[p.row as usize * self.col_count + p.col as usize]
+        }))
+    }
+
+    // Splits the field into regions of orthogonally adjacent nodes considered equal.
+    // Returns a field of region labels (indices into the returned regions) and the regions
+    pub fn connected_components<F>(&self, mut eq: F) -> (Field<usize>, Vec<Region>)
+    where
+        F: FnMut(&Node, &Node) -> bool,
+    {
+        const UNLABELED: usize = usize::MAX;
+
+        let mut labels = Field {
+            nodes: vec![UNLABELED; self.nodes.len()],
+            row_count: self.row_count,
+            col_count: self.col_count,
+        };
+        let mut regions = Vec::new();
+        let mut queue = VecDeque::new();
+
+        for origin in 0..self.nodes.len() {
+            if labels.nodes[origin] != UNLABELED {
+                continue;
+            }
+
+            let label = regions.len();
+            let mut points = Vec::new();
+            labels.nodes[origin] = label;
+            queue.push_back(Point {
+                row: (origin / self.col_count) as i32,
+                col: (origin % self.col_count) as i32,
+            });
+
+            while let Some(point) = queue.pop_front() {
+                let node = self.get(point.row as usize, point.col as usize);
+                for n in point.neighbors_orthogonal() {
+                    let Some(neighbor) = self.try_get_point(&n) else {
+                        continue;
+                    };
+                    let idx = n.row as usize * self.col_count + n.col as usize;
+                    if labels.nodes[idx] != UNLABELED || !eq(node, neighbor) {
+                        continue;
+                    }
+                    labels.nodes[idx] = label;
+                    queue.push_back(n);
+                }
+                points.push(point);
+            }
+
+            regions.push(Region::from_points(points, |p| {
+                labels.try_get_point(p).is_some_and(|l| *l == label)
+            }));
+        }
+
+        (labels, regions)
+    }
+
     fn flat_index(&self, row: usize, col: usize) -> Option<usize> {
         if row >= self.row_count || col >= self.col_count {
             return None;
@@ -72,9 +192,141 @@ where
     }
 }
 
+// One line (row or column) of a field, at(line, i) is the i-th node of it
+type LineAt<'a, Node> = dyn Fn(usize, usize) -> &'a Node + 'a;
+
+impl<Node> Field<Node>
+where
+    Node: Eq,
+{
+    // All mirror lines along the given axis (both when None) with at most
+    // max_mismatches nodes differing from their image. The part of the field
+    // that doesn't fit on the other side of the line is ignored. Horizontal
+    // lines come first, then vertical ones, each from the top or left.
+    // Equal lines are found by comparing every pair, see find_reflections_hashed
+    // for bigger fields
+    pub fn find_reflections(&self, axis: Option<Axis>, max_mismatches: usize) -> Vec<Reflection> {
+        self.reflections_with(axis, max_mismatches, line_ids_by_eq)
+    }
+
+    fn reflections_with<F>(&self, axis: Option<Axis>, max_mismatches: usize, line_ids: F) -> Vec<Reflection>
+    where
+        F: Fn(usize, usize, &LineAt<Node>) -> Vec<usize>,
+    {
+        let mut result = Vec::new();
+        let rows = |row: usize, col: usize| &self.nodes[row * self.col_count + col];
+        let cols = |col: usize, row: usize| &self.nodes[row * self.col_count + col];
+        let lines: [(Axis, usize, usize, &LineAt<Node>); 2] = [
+            (Axis::Horizontal, self.row_count, self.col_count, &rows),
+            (Axis::Vertical, self.col_count, self.row_count, &cols),
+        ];
+        for (line_axis, line_count, line_len, at) in lines {
+            if axis.is_some_and(|axis| axis != line_axis) {
+                continue;
+            }
+            let ids = line_ids(line_count, line_len, at);
+            for (position, mismatches) in find_line_reflections(&ids, line_len, at, max_mismatches) {
+                result.push(Reflection {
+                    axis: line_axis,
+                    position,
+                    mismatches,
+                });
+            }
+        }
+        result
+    }
+}
+
+impl<Node> Field<Node>
+where
+    Node: Eq + Hash,
+{
+    // Same as find_reflections, but every line is hashed once to find the
+    // equal ones, which is linear in the size of the field
+    pub fn find_reflections_hashed(&self, axis: Option<Axis>, max_mismatches: usize) -> Vec<Reflection> {
+        self.reflections_with(axis, max_mismatches, line_ids_by_hash)
+    }
+}
+
+// Equal lines get equal ids, comparing every line with the first one of
+// every distinct kind seen so far
+fn line_ids_by_eq<Node: Eq>(line_count: usize, line_len: usize, at: &LineAt<Node>) -> Vec<usize> {
+    let mut ids = Vec::with_capacity(line_count);
+    let mut distinct: Vec<usize> = Vec::new();
+    for line in 0..line_count {
+        let same = distinct
+            .iter()
+            .position(|&other| (0..line_len).all(|i| at(line, i) == at(other, i)));
+        ids.push(same.unwrap_or_else(|| {
+            distinct.push(line);
+            distinct.len() - 1
+        }));
+    }
+    ids
+}
+
+// Same as line_ids_by_eq, but a line is only compared with the lines of the
+// same hash
+fn line_ids_by_hash<Node: Eq + Hash>(line_count: usize, line_len: usize, at: &LineAt<Node>) -> Vec<usize> {
+    let mut ids = Vec::with_capacity(line_count);
+    // hash -> (first line, id) of every distinct line with this hash
+    let mut buckets: HashMap<u64, Vec<(usize, usize)>> = HashMap::new();
+    let mut next_id = 0;
+    for line in 0..line_count {
+        let mut hasher = DefaultHasher::new();
+        (0..line_len).for_each(|i| at(line, i).hash(&mut hasher));
+        let bucket = buckets.entry(hasher.finish()).or_default();
+        let same = bucket
+            .iter()
+            .find(|(other, _)| (0..line_len).all(|i| at(line, i) == at(*other, i)));
+        let id = match same {
+            Some(&(_, id)) => id,
+            None => {
+                let id = next_id;
+                bucket.push((line, id));
+                next_id += 1;
+                id
+            }
+        };
+        ids.push(id);
+    }
+    ids
+}
+
+// Positions of the mirror lines and their mismatch counts. Lines with equal
+// ids are skipped, the others are compared node by node, but only until the
+// mismatch budget runs out
+fn find_line_reflections<Node: Eq>(
+    ids: &[usize],
+    line_len: usize,
+    at: &LineAt<Node>,
+    max_mismatches: usize,
+) -> Vec<(usize, usize)> {
+    let line_count = ids.len();
+    let mut result = Vec::new();
+    for position in 1..line_count {
+        let mut mismatches = 0;
+        for offset in 0..position.min(line_count - position) {
+            let (a, b) = (position - 1 - offset, position + offset);
+            if ids[a] == ids[b] {
+                continue;
+            }
+            mismatches += (0..line_len).filter(|&i| at(a, i) != at(b, i)).count();
+            if mismatches > max_mismatches {
+                break;
+            }
+        }
+        if mismatches <= max_mismatches {
+            result.push((position, mismatches));
+        }
+    }
+    result
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Field;
+    use super::{Axis, Field, Reflection};
+    use crate::Point;
 
     #[test]
     fn flat_index_test() {
@@ -86,6 +338,40 @@ mod tests {
         assert_eq!(field.flat_index(3, 9), None);
     }
 
+    #[test]
+    fn flood_fill_test() {
+        // cspell: disable-next-line
+        let nodes: Vec<char> = ["AAB", "ABB", "AAB"].concat().chars().collect();
+        let field = Field::from_flat_vector(nodes, 3).unwrap();
+
+        let region = field.flood_fill(Point { row: 0, col: 0 }, |c| *c == 'A').unwrap();
+        assert_eq!(region.area(), 5);
+        assert_eq!(region.perimeter, 12);
+        assert_eq!(region.sides_count, 8);
+        assert_eq!(region.min, Point { row: 0, col: 0 });
+        assert_eq!(region.max, Point { row: 2, col: 1 });
+
+        assert!(field.flood_fill(Point { row: 0, col: 0 }, |c| *c == 'B').is_none());
+        assert!(field.flood_fill(Point { row: 3, col: 0 }, |c| *c == 'A').is_none());
+    }
+
+    #[test]
+    fn connected_components_test() {
+        // cspell: disable-next-line
+        let nodes: Vec<char> = ["AAB", "BAB", "AAC"].concat().chars().collect();
+        let field = Field::from_flat_vector(nodes, 3).unwrap();
+
+        let (labels, regions) = field.connected_components(|a, b| a == b);
+        assert_eq!(regions.len(), 4);
+        assert_eq!(*labels.get(0, 0), *labels.get(2, 0));
+        assert_ne!(*labels.get(0, 2), *labels.get(1, 0));
+        assert_eq!(*labels.get(0, 2), *labels.get(1, 2));
+
+        let areas: Vec<usize> = regions.iter().map(|r| r.area()).collect();
+        assert_eq!(areas, vec![5, 2, 1, 1]);
+        assert_eq!(regions[*labels.get(2, 2)].perimeter, 4);
+    }
+
     #[test]
     fn from_flat_vector_test() {
         let field = Field::from_flat_vector(vec![0, 1, 2, 3, 4, 5], 3);
@@ -164,4 +450,55 @@ mod tests {
         let node = field.try_get(0, 9);
         assert!(node.is_none());
     }
+
+    #[test]
+    fn find_reflections_test() {
+        let field = |rows: &[&str]| {
+            let nodes: Vec<char> = rows.concat().chars().collect();
+            Field::from_flat_vector(nodes, rows[0].len()).unwrap()
+        };
+        let reflection = |axis, position, mismatches| Reflection {
+            axis,
+            position,
+            mismatches,
+        };
+
+        let pattern = field(&[
+            "#.##..##.",
+            "..#.##.#.",
+            "##......#",
+            "##......#",
+            "..#.##.#.",
+            "..##..##.",
+            "#.#.##.#.",
+        ]);
+        assert_eq!(
+            pattern.find_reflections(None, 0),
+            vec![reflection(Axis::Vertical, 5, 0)]
+        );
+        assert_eq!(
+            pattern.find_reflections(Some(Axis::Horizontal), 1),
+            vec![reflection(Axis::Horizontal, 3, 1)]
+        );
+        assert!(pattern.find_reflections(Some(Axis::Horizontal), 0).is_empty());
+        for max_mismatches in 0..4 {
+            assert_eq!(
+                pattern.find_reflections_hashed(None, max_mismatches),
+                pattern.find_reflections(None, max_mismatches)
+            );
+        }
+
+        // works on any comparable nodes, a single row mirrors along every column line
+        let numbers = Field::from_flat_vector(vec![1, 2, 2, 1], 4).unwrap();
+        let found = numbers.find_reflections(Some(Axis::Vertical), 2);
+        assert_eq!(numbers.find_reflections_hashed(Some(Axis::Vertical), 2), found);
+        assert_eq!(
+            found,
+            vec![
+                reflection(Axis::Vertical, 1, 1),
+                reflection(Axis::Vertical, 2, 0),
+                reflection(Axis::Vertical, 3, 1),
+            ]
+        );
+    }
 }
@@ -0,0 +1,189 @@
+// Helpers for simple polygons given as a sequence of vertices (the last vertex
+// is connected back to the first one). Rows are treated as y and cols as x.
+
+use crate::math::gcd;
+use crate::Point;
+
+fn to_i64<I>(point: &Point<I>) -> (i64, i64)
+where
+    I: num_traits::PrimInt,
+{
+    (
+        point.row.to_i64().expect("coordinate doesn't fit into i64"),
+        point.col.to_i64().expect("coordinate doesn't fit into i64"),
+    )
+}
+
+fn edges<I>(vertices: &[Point<I>]) -> impl Iterator<Item = ((i64, i64), (i64, i64))> + '_
+where
+    I: num_traits::PrimInt,
+{
+    let next = vertices.iter().skip(1).chain(vertices.iter().take(1));
+    vertices.iter().zip(next).map(|(a, b)| (to_i64(a), to_i64(b)))
+}
+
+// Twice the polygon area (shoelace formula), which is always an integer for lattice polygons
+pub fn double_area<I>(vertices: &[Point<I>]) -> i64
+where
+    I: num_traits::PrimInt,
+{
+    edges(vertices)
+        .map(|((r1, c1), (r2, c2))| c1 * r2 - c2 * r1)
+        .sum::<i64>()
+        .abs()
+}
+
+pub fn area<I>(vertices: &[Point<I>]) -> f64
+where
+    I: num_traits::PrimInt,
+{
+    double_area(vertices) as f64 / 2.0
+}
+
+// Number of lattice points lying on the polygon edges
+pub fn boundary_points_count<I>(vertices: &[Point<I>]) -> i64
+where
+    I: num_traits::PrimInt,
+{
+    edges(vertices).map(|((r1, c1), (r2, c2))| gcd(r2 - r1, c2 - c1)).sum()
+}
+
+// Number of lattice points strictly inside the polygon, using Pick's theorem:
+// A = i + b / 2 - 1
+pub fn interior_points_count<I>(vertices: &[Point<I>]) -> i64
+where
+    I: num_traits::PrimInt,
+{
+    (double_area(vertices) - boundary_points_count(vertices) + 2) / 2
+}
+
+// Number of lattice points either inside the polygon or on its boundary
+pub fn enclosed_points_count<I>(vertices: &[Point<I>]) -> i64
+where
+    I: num_traits::PrimInt,
+{
+    interior_points_count(vertices) + boundary_points_count(vertices)
+}
+
+fn is_on_segment(point: (i64, i64), a: (i64, i64), b: (i64, i64)) -> bool {
+    let cross = (b.0 - a.0) * (point.1 - a.1) - (b.1 - a.1) * (point.0 - a.0);
+    cross == 0
+        && point.0 >= a.0.min(b.0)
+        && point.0 <= a.0.max(b.0)
+        && point.1 >= a.1.min(b.1)
+        && point.1 <= a.1.max(b.1)
+}
+
+// Ray casting test, the ray goes from the point towards increasing cols.
+// Points lying on the boundary are not considered inside
+pub fn is_point_inside<I>(vertices: &[Point<I>], point: &Point<I>) -> bool
+where
+    I: num_traits::PrimInt,
+{
+    let p = to_i64(point);
+    let mut result = false;
+    for (a, b) in edges(vertices) {
+        if is_on_segment(p, a, b) {
+            return false;
+        }
+        if (a.0 > p.0) == (b.0 > p.0) {
+            continue;
+        }
+        // compare col of the intersection with the point col without division
+        let lhs = (p.1 - a.1) * (b.0 - a.0);
+        let rhs = (p.0 - a.0) * (b.1 - a.1);
+        let crosses = if b.0 > a.0 { lhs < rhs } else { lhs > rhs };
+        if crosses {
+            result = !result;
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        area, boundary_points_count, double_area, enclosed_points_count, interior_points_count, is_point_inside,
+    };
+    use crate::Point;
+
+    fn polygon(vertices: &[(i32, i32)]) -> Vec<Point<i32>> {
+        vertices.iter().map(|&(row, col)| Point { row, col }).collect()
+    }
+
+    #[test]
+    fn square_test() {
+        let square = polygon(&[(0, 0), (0, 4), (4, 4), (4, 0)]);
+        assert_eq!(double_area(&square), 32);
+        assert_eq!(area(&square), 16.0);
+        assert_eq!(boundary_points_count(&square), 16);
+        assert_eq!(interior_points_count(&square), 9);
+        assert_eq!(enclosed_points_count(&square), 25);
+
+        // orientation doesn't matter
+        let reversed: Vec<Point<i32>> = square.iter().rev().copied().collect();
+        assert_eq!(double_area(&reversed), 32);
+    }
+
+    #[test]
+    fn triangle_test() {
+        let triangle = polygon(&[(0, 0), (0, 3), (3, 0)]);
+        assert_eq!(double_area(&triangle), 9);
+        assert_eq!(boundary_points_count(&triangle), 9);
+        assert_eq!(interior_points_count(&triangle), 1);
+    }
+
+    #[test]
+    fn lagoon_test() {
+        // cspell: disable
+        // #######
+        // #.....#
+        // ###...#
+        // ..#...#
+        // ..#...#
+        // ###.###
+        // #...#..
+        // ##..###
+        // .#....#
+        // .######
+        // cspell: enable
+        let lagoon = polygon(&[
+            (0, 0),
+            (0, 6),
+            (5, 6),
+            (5, 4),
+            (7, 4),
+            (7, 6),
+            (9, 6),
+            (9, 1),
+            (7, 1),
+            (7, 0),
+            (5, 0),
+            (5, 2),
+            (2, 2),
+            (2, 0),
+        ]);
+        assert_eq!(boundary_points_count(&lagoon), 38);
+        assert_eq!(enclosed_points_count(&lagoon), 62);
+    }
+
+    #[test]
+    fn is_point_inside_test() {
+        let shape = polygon(&[(0, 0), (0, 4), (4, 4), (4, 2), (2, 2), (2, 0)]);
+        assert!(is_point_inside(&shape, &Point { row: 1, col: 1 }));
+        assert!(is_point_inside(&shape, &Point { row: 3, col: 3 }));
+        assert!(!is_point_inside(&shape, &Point { row: 3, col: 1 }));
+        assert!(!is_point_inside(&shape, &Point { row: 5, col: 5 }));
+        assert!(!is_point_inside(&shape, &Point { row: -1, col: 1 }));
+
+        // boundary and vertices
+        assert!(!is_point_inside(&shape, &Point { row: 0, col: 2 }));
+        assert!(!is_point_inside(&shape, &Point { row: 2, col: 2 }));
+        assert!(!is_point_inside(&shape, &Point { row: 3, col: 2 }));
+
+        // the ray goes along an edge or passes through a vertex
+        assert!(!is_point_inside(&shape, &Point { row: 4, col: 1 }));
+        assert!(is_point_inside(&shape, &Point { row: 2, col: 3 }));
+        assert!(is_point_inside(&shape, &Point { row: 1, col: 3 }));
+    }
+}
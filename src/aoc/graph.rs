@@ -0,0 +1,203 @@
+// Directed graph helpers for dependency puzzles: "a must come before b" rules,
+// build orders and the like. Nodes are small Copy values (page numbers, step
+// letters), edges go from the node that must come first.
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fmt;
+use std::hash::Hash;
+
+// Nodes that depend on each other in a circle: every node must come
+// before the next one and the last one before the first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError<N> {
+    pub nodes: Vec<N>,
+}
+
+impl<N: fmt::Debug> fmt::Display for CycleError<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "dependency cycle {:?}", self.nodes)
+    }
+}
+
+impl<N: fmt::Debug> std::error::Error for CycleError<N> {}
+
+// Kahn's algorithm. Whenever several nodes are ready, the one listed first in
+// nodes goes first, so the sort is stable: nodes that don't depend on each
+// other keep their order. Edges touching nodes that aren't listed are ignored,
+// which restricts the rules to the given subset (dependencies that go through
+// the left out nodes are lost too)
+pub fn toposort<N, I>(nodes: &[N], edges: I) -> Result<Vec<N>, CycleError<N>>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = (N, N)>,
+{
+    let index: HashMap<N, usize> = nodes.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+    let mut successors: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+    let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+    let mut in_degree = vec![0; nodes.len()];
+    let mut seen_edges = HashSet::new();
+    for (from, to) in edges {
+        let (Some(&from), Some(&to)) = (index.get(&from), index.get(&to)) else {
+            continue;
+        };
+        if seen_edges.insert((from, to)) {
+            successors[from].push(to);
+            predecessors[to].push(from);
+            in_degree[to] += 1;
+        }
+    }
+
+    let mut ready: BinaryHeap<Reverse<usize>> = (0..nodes.len()).filter(|&i| in_degree[i] == 0).map(Reverse).collect();
+    let mut result = Vec::with_capacity(nodes.len());
+    while let Some(Reverse(i)) = ready.pop() {
+        result.push(nodes[i]);
+        for &next in successors[i].iter() {
+            in_degree[next] -= 1;
+            if in_degree[next] == 0 {
+                ready.push(Reverse(next));
+            }
+        }
+    }
+
+    if result.len() == nodes.len() {
+        return Ok(result);
+    }
+
+    // Every node left has a predecessor that is left too, so walking
+    // predecessors from any of them has to come back to a visited node
+    let mut position = vec![usize::MAX; nodes.len()];
+    let mut path = Vec::new();
+    let mut current = (0..nodes.len()).find(|&i| in_degree[i] > 0).unwrap();
+    while position[current] == usize::MAX {
+        position[current] = path.len();
+        path.push(current);
+        current = *predecessors[current].iter().find(|&&p| in_degree[p] > 0).unwrap();
+    }
+    let mut cycle: Vec<N> = path[position[current]..].iter().map(|&i| nodes[i]).collect();
+    cycle.reverse();
+    Err(CycleError { nodes: cycle })
+}
+
+// A set of "a comes before b" rules. Only pairs that have a rule are ordered,
+// the rules don't have to be transitive or cover every pair
+#[derive(Debug, Clone, Default)]
+pub struct PartialOrder<N> {
+    rules: HashSet<(N, N)>,
+}
+
+impl<N> PartialOrder<N>
+where
+    N: Copy + Eq + Hash,
+{
+    pub fn new<I: IntoIterator<Item = (N, N)>>(rules: I) -> Self {
+        PartialOrder {
+            rules: rules.into_iter().collect(),
+        }
+    }
+
+    // There's a rule that a comes before b
+    pub fn is_before(&self, a: N, b: N) -> bool {
+        self.rules.contains(&(a, b))
+    }
+
+    // Compares a single pair, pairs without a rule are Equal. That's not a
+    // total order, so it must not be passed to sort_by: the result may be
+    // wrong or the sort may panic. Use sort() for ordering a list
+    pub fn compare(&self, a: &N, b: &N) -> Ordering {
+        if self.is_before(*a, *b) {
+            Ordering::Less
+        } else if self.is_before(*b, *a) {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }
+
+    // No rule is broken by any pair of the nodes
+    pub fn is_sorted(&self, nodes: &[N]) -> bool {
+        nodes
+            .iter()
+            .enumerate()
+            .all(|(i, &a)| nodes[i + 1..].iter().all(|&b| !self.is_before(b, a)))
+    }
+
+    // The rules between the given nodes only
+    pub fn restrict(&self, nodes: &[N]) -> PartialOrder<N> {
+        let nodes: HashSet<N> = nodes.iter().copied().collect();
+        PartialOrder {
+            rules: self
+                .rules
+                .iter()
+                .filter(|(a, b)| nodes.contains(a) && nodes.contains(b))
+                .copied()
+                .collect(),
+        }
+    }
+
+    // Reorders the nodes to satisfy the rules between them, see toposort
+    pub fn sort(&self, nodes: &[N]) -> Result<Vec<N>, CycleError<N>> {
+        toposort(nodes, self.rules.iter().copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use super::{toposort, CycleError, PartialOrder};
+
+    #[test]
+    fn toposort_test() {
+        let edges = vec![
+            ('c', 'a'),
+            ('a', 'b'),
+            ('c', 'f'),
+            ('a', 'd'),
+            ('b', 'e'),
+            ('d', 'e'),
+            ('f', 'e'),
+        ];
+        let nodes: Vec<char> = "abcdef".chars().collect();
+        assert_eq!(
+            toposort(&nodes, edges.clone()).unwrap(),
+            vec!['c', 'a', 'b', 'd', 'f', 'e']
+        );
+
+        // nodes without rules between them keep their order
+        assert_eq!(
+            toposort(&['x', 'b', 'y', 'a'], edges.clone()).unwrap(),
+            vec!['x', 'y', 'a', 'b']
+        );
+        // c comes before e only through f, which is left out
+        assert_eq!(toposort(&['e', 'c'], edges).unwrap(), vec!['e', 'c']);
+    }
+
+    #[test]
+    fn cycle_test() {
+        let edges = vec![(1, 2), (2, 3), (3, 4), (4, 2), (0, 1)];
+        let err = toposort(&[0, 1, 2, 3, 4], edges.clone()).unwrap_err();
+        assert_eq!(err.nodes.len(), 3);
+        for (i, node) in err.nodes.iter().enumerate() {
+            let next = err.nodes[(i + 1) % err.nodes.len()];
+            assert!(edges.contains(&(*node, next)));
+        }
+        assert_eq!(toposort(&[7], vec![(7, 7)]), Err(CycleError { nodes: vec![7] }));
+
+        // the cycle is outside of the subset
+        assert_eq!(toposort(&[0, 1, 2], edges).unwrap(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn partial_order_test() {
+        let order = PartialOrder::new(vec![(47, 53), (97, 13), (97, 61), (61, 13), (75, 13)]);
+        assert_eq!(order.compare(&47, &53), Ordering::Less);
+        assert_eq!(order.compare(&13, &97), Ordering::Greater);
+        assert_eq!(order.compare(&47, &13), Ordering::Equal);
+
+        assert!(order.is_sorted(&[97, 61, 13]));
+        assert!(!order.is_sorted(&[97, 13, 61]));
+        assert_eq!(order.sort(&[13, 75, 61, 97]).unwrap(), vec![75, 97, 61, 13]);
+        assert_eq!(order.restrict(&[97, 13, 47]).rules.len(), 1);
+    }
+}
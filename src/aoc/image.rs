@@ -0,0 +1,324 @@
+// Export of grids and simulation frames as images, one pixel per node
+// (use scaled() to make them bigger). Handy for puzzles where the answer
+// has to be confirmed by looking at it, like the 2024 day 14 tree. Solutions
+// call save(), which writes a PNG only when the runner was started with
+// --image-dir, so it's fine to leave it in.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::{Field, Point};
+
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pixels: Vec<Rgb>,
+    width: usize,
+    height: usize,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Image {
+            pixels: vec![background; width * height],
+            width,
+            height,
+        }
+    }
+
+    // Rows become y and cols become x
+    pub fn from_field<Node, F>(field: &Field<Node>, mut color: F) -> Self
+    where
+        Node: Default + Clone,
+        F: FnMut(&Node) -> Rgb,
+    {
+        let (height, width) = (field.get_row_count(), field.get_col_count());
+        let mut pixels = Vec::with_capacity(width * height);
+        for row in 0..height {
+            for col in 0..width {
+                pixels.push(color(field.get(row, col)));
+            }
+        }
+        Image { pixels, width, height }
+    }
+
+    // Points outside of the image are skipped
+    pub fn from_points<I>(row_count: usize, col_count: usize, points: I, on: Rgb, off: Rgb) -> Self
+    where
+        I: IntoIterator<Item = Point<i32>>,
+    {
+        let mut result = Image::new(col_count, row_count, off);
+        for point in points {
+            result.set(&point, on);
+        }
+        result
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, point: &Point<i32>) -> Option<Rgb> {
+        self.index(point).map(|i| self.pixels[i])
+    }
+
+    // Does nothing for points outside of the image
+    pub fn set(&mut self, point: &Point<i32>, color: Rgb) {
+        if let Some(i) = self.index(point) {
+            self.pixels[i] = color;
+        }
+    }
+
+    fn index(&self, point: &Point<i32>) -> Option<usize> {
+        if point.row < 0 || point.col < 0 {
+            return None;
+        }
+        let (row, col) = (point.row as usize, point.col as usize);
+        if row < self.height && col < self.width {
+            Some(row * self.width + col)
+        } else {
+            None
+        }
+    }
+
+    // Every pixel becomes a factor x factor square
+    pub fn scaled(&self, factor: usize) -> Image {
+        let mut result = Image::new(self.width * factor, self.height * factor, BLACK);
+        for (i, pixel) in result.pixels.iter_mut().enumerate() {
+            let (row, col) = (i / result.width / factor, i % result.width / factor);
+            *pixel = self.pixels[row * self.width + col];
+        }
+        result
+    }
+
+    // Copies the other image into this one, the top left corner goes to the given point
+    pub fn blit(&mut self, other: &Image, top_left: &Point<i32>) {
+        for row in 0..other.height {
+            for col in 0..other.width {
+                let target = Point {
+                    row: top_left.row + row as i32,
+                    col: top_left.col + col as i32,
+                };
+                self.set(&target, other.pixels[row * other.width + col]);
+            }
+        }
+    }
+
+    fn rgb_bytes(&self) -> Vec<u8> {
+        self.pixels.iter().flatten().copied().collect()
+    }
+
+    // Binary PPM (P6), the simplest format that any image viewer opens
+    pub fn write_ppm<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        write!(writer, "P6\n{} {}\n255\n", self.width, self.height)?;
+        writer.write_all(&self.rgb_bytes())
+    }
+
+    pub fn write_png<W: Write>(&self, writer: W) -> io::Result<()> {
+        let (width, height) = dimensions::<u32>(self)?;
+        let mut encoder = png::Encoder::new(writer, width, height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.rgb_bytes())?;
+        Ok(())
+    }
+
+    pub fn save_ppm(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.write_ppm(BufWriter::new(File::create(path)?))
+    }
+
+    pub fn save_png(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.write_png(BufWriter::new(File::create(path)?))
+    }
+}
+
+// Lays the frames out in a grid with the given number of columns.
+// Frames may have different sizes, every cell gets the size of the largest one
+pub fn contact_sheet(frames: &[Image], columns: usize, gap: usize, background: Rgb) -> Image {
+    assert!(columns > 0);
+    let cell_width = frames.iter().map(|f| f.width).max().unwrap_or(0);
+    let cell_height = frames.iter().map(|f| f.height).max().unwrap_or(0);
+    let rows = frames.len().div_ceil(columns);
+    let columns = columns.min(frames.len());
+
+    let width = (columns * (cell_width + gap)).saturating_sub(gap);
+    let height = (rows * (cell_height + gap)).saturating_sub(gap);
+    let mut result = Image::new(width, height, background);
+    for (i, frame) in frames.iter().enumerate() {
+        let top_left = Point {
+            row: ((i / columns) * (cell_height + gap)) as i32,
+            col: ((i % columns) * (cell_width + gap)) as i32,
+        };
+        result.blit(frame, &top_left);
+    }
+    result
+}
+
+// Width and height in the type the format stores them in, an error if they don't fit
+fn dimensions<T: TryFrom<usize>>(image: &Image) -> io::Result<(T, T)> {
+    match (T::try_from(image.width), T::try_from(image.height)) {
+        (Ok(width), Ok(height)) => Ok((width, height)),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("image is too large: {}x{}", image.width, image.height),
+        )),
+    }
+}
+
+fn gif_error(err: gif::EncodingError) -> io::Error {
+    match err {
+        gif::EncodingError::Io(err) => err,
+        other => io::Error::other(other),
+    }
+}
+
+// Animated GIF that loops forever. All frames must have the same size.
+// Colors are quantized to a 256 color palette per frame
+pub fn write_gif<W: Write>(writer: W, frames: &[Image], frame_delay_ms: u16) -> io::Result<()> {
+    let Some(first) = frames.first() else {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "no frames to write"));
+    };
+    let (width, height) = dimensions::<u16>(first)?;
+    let mut encoder = gif::Encoder::new(writer, width, height, &[]).map_err(gif_error)?;
+    encoder.set_repeat(gif::Repeat::Infinite).map_err(gif_error)?;
+    for image in frames.iter() {
+        if (image.width, image.height) != (first.width, first.height) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "frames have different sizes",
+            ));
+        }
+        let mut frame = gif::Frame::from_rgb(width, height, &image.rgb_bytes());
+        // GIF delays are in hundredths of a second
+        frame.delay = frame_delay_ms / 10;
+        encoder.write_frame(&frame).map_err(gif_error)?;
+    }
+    Ok(())
+}
+
+pub fn save_gif(path: impl AsRef<Path>, frames: &[Image], frame_delay_ms: u16) -> io::Result<()> {
+    write_gif(BufWriter::new(File::create(path)?), frames, frame_delay_ms)
+}
+
+static IMAGE_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+// Called by the runner, the directory is created if needed
+pub fn configure(dir: PathBuf) {
+    std::fs::create_dir_all(&dir).unwrap_or_else(|err| panic!("Failed to create {}: {}", dir.display(), err));
+    *IMAGE_DIR.lock().unwrap() = Some(dir);
+}
+
+pub fn is_enabled() -> bool {
+    IMAGE_DIR.lock().unwrap().is_some()
+}
+
+// Writes the image to <name>.png in the image directory, does nothing
+// unless it's configured
+pub fn save(name: &str, image: &Image) -> io::Result<()> {
+    let Some(dir) = IMAGE_DIR.lock().unwrap().clone() else {
+        return Ok(());
+    };
+    image.save_png(dir.join(format!("{}.png", name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{contact_sheet, write_gif, Image, BLACK, WHITE};
+    use crate::{Field, Point};
+
+    const RED: [u8; 3] = [255, 0, 0];
+
+    fn test_image() -> Image {
+        let points = vec![Point { row: 0, col: 0 }, Point { row: 1, col: 2 }];
+        Image::from_points(2, 3, points, WHITE, BLACK)
+    }
+
+    #[test]
+    fn from_field_test() {
+        let field = Field::from_flat_vector(vec![1, 0, 0, 0, 0, 1], 3).unwrap();
+        let image = Image::from_field(&field, |&n| if n == 1 { WHITE } else { BLACK });
+        assert_eq!(image, test_image());
+        assert_eq!((image.width(), image.height()), (3, 2));
+        assert_eq!(image.get(&Point { row: 1, col: 2 }), Some(WHITE));
+        assert_eq!(image.get(&Point { row: 2, col: 0 }), None);
+    }
+
+    #[test]
+    fn ppm_test() {
+        let mut bytes = Vec::new();
+        test_image().write_ppm(&mut bytes).unwrap();
+        let mut expected = b"P6\n3 2\n255\n".to_vec();
+        expected.extend([255, 255, 255, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 255, 255, 255]);
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn png_test() {
+        let mut image = test_image();
+        image.set(&Point { row: 0, col: 1 }, RED);
+        let mut bytes = Vec::new();
+        image.write_png(&mut bytes).unwrap();
+
+        let decoder = png::Decoder::new(bytes.as_slice());
+        let mut reader = decoder.read_info().unwrap();
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buffer).unwrap();
+        assert_eq!((info.width, info.height), (3, 2));
+        assert_eq!(&buffer[..9], &[255, 255, 255, 255, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn scaled_test() {
+        let image = test_image().scaled(2);
+        assert_eq!((image.width(), image.height()), (6, 4));
+        assert_eq!(image.get(&Point { row: 1, col: 1 }), Some(WHITE));
+        assert_eq!(image.get(&Point { row: 1, col: 2 }), Some(BLACK));
+        assert_eq!(image.get(&Point { row: 3, col: 5 }), Some(WHITE));
+    }
+
+    #[test]
+    fn contact_sheet_test() {
+        let frames = vec![test_image(), test_image(), test_image()];
+        let sheet = contact_sheet(&frames, 2, 1, RED);
+        assert_eq!((sheet.width(), sheet.height()), (7, 5));
+        assert_eq!(sheet.get(&Point { row: 0, col: 4 }), Some(WHITE));
+        assert_eq!(sheet.get(&Point { row: 0, col: 3 }), Some(RED));
+        assert_eq!(sheet.get(&Point { row: 4, col: 2 }), Some(WHITE));
+        assert_eq!(sheet.get(&Point { row: 4, col: 6 }), Some(RED));
+    }
+
+    #[test]
+    fn gif_test() {
+        let mut second = test_image();
+        second.set(&Point { row: 0, col: 1 }, RED);
+        let mut bytes = Vec::new();
+        write_gif(&mut bytes, &[test_image(), second], 100).unwrap();
+        assert!(bytes.starts_with(b"GIF89a"));
+
+        let mut decoder = gif::DecodeOptions::new().read_info(bytes.as_slice()).unwrap();
+        let mut frame_count = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!((frame.width, frame.height, frame.delay), (3, 2, 10));
+            frame_count += 1;
+        }
+        assert_eq!(frame_count, 2);
+
+        assert!(write_gif(&mut Vec::new(), &[], 100).is_err());
+        assert!(write_gif(&mut Vec::new(), &[test_image(), test_image().scaled(2)], 100).is_err());
+
+        let wide = Image::new(70000, 1, BLACK);
+        let err = write_gif(&mut Vec::new(), &[wide], 100).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    }
+}
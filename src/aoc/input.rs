@@ -0,0 +1,146 @@
+// Input reading and lazy splitting helpers. Only the runner reads input files,
+// puzzle modules get the text and split it with the helpers below, which
+// borrow from it, so walking over lines and numbers doesn't allocate.
+
+use std::str::FromStr;
+
+use crate::Field;
+
+// The whole input file
+pub fn read(file_name: &str) -> String {
+    std::fs::read_to_string(file_name).unwrap_or_else(|err| panic!("Failed to read {}: {}", file_name, err))
+}
+
+// Substrings that look like integers. A minus sign counts only when it isn't
+// preceded by a digit, so "10-20" gives "10" and "20"
+pub struct IntTokens<'a> {
+    line: &'a str,
+    pos: usize,
+}
+
+impl<'a> Iterator for IntTokens<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let bytes = self.line.as_bytes();
+        while self.pos < bytes.len() {
+            let i = self.pos;
+            let negative = bytes[i] == b'-'
+                && i + 1 < bytes.len()
+                && bytes[i + 1].is_ascii_digit()
+                && (i == 0 || !bytes[i - 1].is_ascii_digit());
+            if !negative && !bytes[i].is_ascii_digit() {
+                self.pos += 1;
+                continue;
+            }
+            self.pos += 1;
+            while self.pos < bytes.len() && bytes[self.pos].is_ascii_digit() {
+                self.pos += 1;
+            }
+            return Some(&self.line[i..self.pos]);
+        }
+        None
+    }
+}
+
+pub fn int_tokens(line: &str) -> IntTokens<'_> {
+    IntTokens { line, pos: 0 }
+}
+
+// All integers on the line, everything else is ignored.
+// Panics if a number doesn't fit into T, use aoc::parse::ints to get an error instead
+pub fn ints<T: FromStr>(line: &str) -> impl Iterator<Item = T> + '_ {
+    int_tokens(line).map(|token| crate::parse_or_panic(token))
+}
+
+// Every character of the line as a single decimal digit, panics on anything else
+pub fn digits(line: &str) -> impl Iterator<Item = u8> + '_ {
+    line.chars().map(|ch| {
+        let digit = ch.to_digit(10).unwrap_or_else(|| panic!("'{}' is not a digit", ch));
+        digit as u8
+    })
+}
+
+// Blocks of lines separated by one or more blank lines, without the trailing line break
+pub struct Paragraphs<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Iterator for Paragraphs<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let mut start = None;
+        let mut end = 0;
+        let mut offset = 0;
+        for line in self.rest.split_inclusive('\n') {
+            let content = line.trim_end_matches(['\n', '\r']);
+            if content.trim().is_empty() {
+                if start.is_some() {
+                    break;
+                }
+            } else {
+                start.get_or_insert(offset);
+                end = offset + content.len();
+            }
+            offset += line.len();
+        }
+        let text = self.rest;
+        self.rest = &text[offset..];
+        start.map(|start| &text[start..end])
+    }
+}
+
+pub fn paragraphs(text: &str) -> Paragraphs<'_> {
+    Paragraphs { rest: text }
+}
+
+// Blank line separated character grids, one Field per block
+pub fn grid_blocks(text: &str) -> impl Iterator<Item = Field<char>> + '_ {
+    paragraphs(text).map(|block| {
+        let col_count = block.lines().next().map_or(0, |line| line.chars().count());
+        assert!(
+            block.lines().all(|line| line.chars().count() == col_count),
+            "grid rows have different lengths"
+        );
+        let nodes: Vec<char> = block.lines().flat_map(|line| line.chars()).collect();
+        Field::from_flat_vector(nodes, col_count).unwrap()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{digits, grid_blocks, ints, paragraphs};
+
+    #[test]
+    fn ints_test() {
+        assert_eq!(ints::<i32>("p=0,4 v=3,-3").collect::<Vec<_>>(), vec![0, 4, 3, -3]);
+        assert_eq!(ints::<u32>("10-20").collect::<Vec<_>>(), vec![10, 20]);
+        assert_eq!(ints::<i32>("-5 - 3 -").collect::<Vec<_>>(), vec![-5, 3]);
+        assert_eq!(ints::<i32>("no numbers").count(), 0);
+    }
+
+    #[test]
+    fn digits_test() {
+        assert_eq!(digits("9071").collect::<Vec<_>>(), vec![9, 0, 7, 1]);
+        assert_eq!(digits("").count(), 0);
+    }
+
+    #[test]
+    fn paragraphs_test() {
+        let text = "a\nb\n\n\nc\r\n\r\nd\ne\n";
+        assert_eq!(paragraphs(text).collect::<Vec<_>>(), vec!["a\nb", "c", "d\ne"]);
+        assert_eq!(paragraphs("\n\n").count(), 0);
+        assert_eq!(paragraphs("").count(), 0);
+    }
+
+    #[test]
+    fn grid_blocks_test() {
+        let grids: Vec<_> = grid_blocks("#.#\n..#\n\n##\n#.\n.#\n").collect();
+        assert_eq!(grids.len(), 2);
+        assert_eq!((grids[0].get_row_count(), grids[0].get_col_count()), (2, 3));
+        assert_eq!(*grids[0].get(1, 2), '#');
+        assert_eq!((grids[1].get_row_count(), grids[1].get_col_count()), (3, 2));
+        assert_eq!(*grids[1].get(2, 0), '.');
+    }
+}
@@ -0,0 +1,362 @@
+use num_traits::PrimInt;
+
+// Half-open interval [start, end)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval<I> {
+    pub start: I,
+    pub end: I,
+}
+
+impl<I> Interval<I>
+where
+    I: PrimInt,
+{
+    pub fn new(start: I, end: I) -> Self {
+        Interval { start, end }
+    }
+
+    pub fn from_start_len(start: I, len: I) -> Self {
+        Interval {
+            start,
+            end: start + len,
+        }
+    }
+
+    // Both ends included: [first, last]
+    pub fn from_inclusive(first: I, last: I) -> Self {
+        Interval {
+            start: first,
+            end: last + I::one(),
+        }
+    }
+
+    pub fn len(&self) -> I {
+        if self.is_empty() {
+            I::zero()
+        } else {
+            self.end - self.start
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    pub fn contains(&self, value: I) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    pub fn intersection(&self, other: &Interval<I>) -> Option<Interval<I>> {
+        let result = Interval {
+            start: self.start.max(other.start),
+            end: self.end.min(other.end),
+        };
+        if result.is_empty() {
+            None
+        } else {
+            Some(result)
+        }
+    }
+
+    pub fn overlaps(&self, other: &Interval<I>) -> bool {
+        self.intersection(other).is_some()
+    }
+}
+
+// Sorts the intervals and merges overlapping and adjacent ones. Empty intervals are dropped
+pub fn merge<I, It>(intervals: It) -> Vec<Interval<I>>
+where
+    I: PrimInt,
+    It: IntoIterator<Item = Interval<I>>,
+{
+    let mut intervals: Vec<Interval<I>> = intervals.into_iter().filter(|i| !i.is_empty()).collect();
+    intervals.sort();
+
+    let mut result: Vec<Interval<I>> = Vec::with_capacity(intervals.len());
+    for interval in intervals {
+        match result.last_mut() {
+            Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+            _ => result.push(interval),
+        }
+    }
+    result
+}
+
+// A set of values stored as sorted, disjoint and non-adjacent intervals
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntervalSet<I> {
+    intervals: Vec<Interval<I>>,
+}
+
+impl<I> Default for IntervalSet<I> {
+    fn default() -> Self {
+        IntervalSet { intervals: Vec::new() }
+    }
+}
+
+impl<I> FromIterator<Interval<I>> for IntervalSet<I>
+where
+    I: PrimInt,
+{
+    fn from_iter<It: IntoIterator<Item = Interval<I>>>(iter: It) -> Self {
+        IntervalSet { intervals: merge(iter) }
+    }
+}
+
+impl<I> IntervalSet<I>
+where
+    I: PrimInt,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn intervals(&self) -> &[Interval<I>] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn insert(&mut self, interval: Interval<I>) {
+        let intervals = std::mem::take(&mut self.intervals);
+        self.intervals = merge(intervals.into_iter().chain(std::iter::once(interval)));
+    }
+
+    pub fn contains(&self, value: I) -> bool {
+        let idx = self.intervals.partition_point(|i| i.end <= value);
+        idx < self.intervals.len() && self.intervals[idx].contains(value)
+    }
+
+    pub fn min(&self) -> Option<I> {
+        self.intervals.first().map(|i| i.start)
+    }
+
+    // Number of values in the set
+    pub fn total_length(&self) -> I {
+        self.intervals.iter().fold(I::zero(), |acc, i| acc + i.len())
+    }
+
+    pub fn union(&self, other: &IntervalSet<I>) -> IntervalSet<I> {
+        self.intervals.iter().chain(other.intervals.iter()).copied().collect()
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<I>) -> IntervalSet<I> {
+        let mut result = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a, b) = (&self.intervals[i], &other.intervals[j]);
+            if let Some(common) = a.intersection(b) {
+                result.push(common);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { intervals: result }
+    }
+
+    pub fn difference(&self, other: &IntervalSet<I>) -> IntervalSet<I> {
+        let mut result = Vec::new();
+        let mut j = 0;
+        for interval in self.intervals.iter() {
+            let mut start = interval.start;
+            // skip the intervals that end before the current one starts
+            while j < other.intervals.len() && other.intervals[j].end <= start {
+                j += 1;
+            }
+            let mut k = j;
+            while k < other.intervals.len() && other.intervals[k].start < interval.end {
+                let removed = &other.intervals[k];
+                if removed.start > start {
+                    result.push(Interval::new(start, removed.start));
+                }
+                start = start.max(removed.end);
+                k += 1;
+            }
+            if start < interval.end {
+                result.push(Interval::new(start, interval.end));
+            }
+        }
+        IntervalSet { intervals: result }
+    }
+}
+
+// A single piece of a piecewise-linear map: shifts values of the source
+// interval so that source.start is mapped to dest_start
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MapRule<I> {
+    pub source: Interval<I>,
+    pub dest_start: I,
+}
+
+impl<I> MapRule<I>
+where
+    I: PrimInt,
+{
+    fn apply(&self, value: I) -> I {
+        value - self.source.start + self.dest_start
+    }
+}
+
+// Piecewise-linear map, values not covered by any rule are mapped to themselves.
+// If rules overlap, the first added one wins
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntervalMap<I> {
+    rules: Vec<MapRule<I>>,
+}
+
+impl<I> Default for IntervalMap<I> {
+    fn default() -> Self {
+        IntervalMap { rules: Vec::new() }
+    }
+}
+
+impl<I> IntervalMap<I>
+where
+    I: PrimInt,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, source: Interval<I>, dest_start: I) {
+        self.rules.push(MapRule { source, dest_start });
+    }
+
+    pub fn rules(&self) -> &[MapRule<I>] {
+        &self.rules
+    }
+
+    pub fn map_value(&self, value: I) -> I {
+        match self.rules.iter().find(|r| r.source.contains(value)) {
+            Some(rule) => rule.apply(value),
+            None => value,
+        }
+    }
+
+    // Splits the interval on the rule boundaries and maps every piece separately
+    pub fn map_interval(&self, interval: &Interval<I>) -> Vec<Interval<I>> {
+        let mut result = Vec::new();
+        let mut unmapped = vec![*interval];
+        for rule in self.rules.iter() {
+            let mut rest = Vec::with_capacity(unmapped.len());
+            for piece in unmapped.into_iter() {
+                let Some(common) = piece.intersection(&rule.source) else {
+                    rest.push(piece);
+                    continue;
+                };
+                result.push(Interval::new(rule.apply(common.start), rule.apply(common.end)));
+                if piece.start < common.start {
+                    rest.push(Interval::new(piece.start, common.start));
+                }
+                if common.end < piece.end {
+                    rest.push(Interval::new(common.end, piece.end));
+                }
+            }
+            unmapped = rest;
+        }
+        result.extend(unmapped.into_iter().filter(|i| !i.is_empty()));
+        result
+    }
+
+    pub fn map_set(&self, set: &IntervalSet<I>) -> IntervalSet<I> {
+        set.intervals().iter().flat_map(|i| self.map_interval(i)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{merge, Interval, IntervalMap, IntervalSet};
+
+    fn set(intervals: &[(i32, i32)]) -> IntervalSet<i32> {
+        intervals.iter().map(|&(s, e)| Interval::new(s, e)).collect()
+    }
+
+    #[test]
+    fn interval_test() {
+        let interval = Interval::new(3, 7);
+        assert_eq!(interval.len(), 4);
+        assert!(interval.contains(3));
+        assert!(!interval.contains(7));
+        assert_eq!(Interval::from_start_len(3, 4), interval);
+        assert_eq!(Interval::from_inclusive(3, 6), interval);
+
+        assert_eq!(interval.intersection(&Interval::new(5, 10)), Some(Interval::new(5, 7)));
+        assert_eq!(interval.intersection(&Interval::new(7, 10)), None);
+        assert!(!interval.overlaps(&Interval::new(0, 3)));
+
+        assert!(Interval::new(5u64, 5).is_empty());
+        assert_eq!(Interval::new(5u64, 2).len(), 0);
+    }
+
+    #[test]
+    fn merge_test() {
+        let merged = merge(vec![
+            Interval::new(10, 12),
+            Interval::new(1, 3),
+            Interval::new(2, 5),
+            Interval::new(5, 6),
+            Interval::new(8, 8),
+        ]);
+        assert_eq!(merged, vec![Interval::new(1, 6), Interval::new(10, 12)]);
+    }
+
+    #[test]
+    fn interval_set_test() {
+        let mut s = set(&[(1, 3), (5, 8)]);
+        assert_eq!(s.total_length(), 5);
+        assert!(s.contains(1));
+        assert!(!s.contains(3));
+        assert!(s.contains(7));
+        assert!(!s.contains(8));
+        assert_eq!(s.min(), Some(1));
+
+        s.insert(Interval::new(3, 5));
+        assert_eq!(s.intervals(), &[Interval::new(1, 8)]);
+    }
+
+    #[test]
+    fn set_operations_test() {
+        let a = set(&[(0, 5), (10, 15)]);
+        let b = set(&[(3, 12), (14, 20)]);
+
+        assert_eq!(a.union(&b), set(&[(0, 20)]));
+        assert_eq!(a.intersection(&b), set(&[(3, 5), (10, 12), (14, 15)]));
+        assert_eq!(a.difference(&b), set(&[(0, 3), (12, 14)]));
+        assert_eq!(b.difference(&a), set(&[(5, 10), (15, 20)]));
+        assert_eq!(a.difference(&a), IntervalSet::new());
+        assert_eq!(a.difference(&IntervalSet::new()), a);
+    }
+
+    #[test]
+    fn interval_map_test() {
+        // seed-to-soil map from 2023 day 5
+        let mut map = IntervalMap::new();
+        map.add(Interval::from_start_len(98u64, 2), 50);
+        map.add(Interval::from_start_len(50, 48), 52);
+
+        assert_eq!(map.map_value(79), 81);
+        assert_eq!(map.map_value(14), 14);
+        assert_eq!(map.map_value(98), 50);
+        assert_eq!(map.map_value(100), 100);
+
+        let mut mapped = map.map_interval(&Interval::new(40, 100));
+        mapped.sort();
+        assert_eq!(
+            mapped,
+            vec![Interval::new(40, 50), Interval::new(50, 52), Interval::new(52, 100)]
+        );
+
+        let seeds: IntervalSet<u64> = [Interval::from_start_len(79, 14), Interval::from_start_len(55, 13)]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            map.map_set(&seeds).intervals(),
+            &[Interval::new(57, 70), Interval::new(81, 95)]
+        );
+    }
+}
@@ -0,0 +1,336 @@
+// Number theory helpers. Anything that may overflow in the middle of the
+// computation (products of residues, determinants) is done in i128
+
+use num_traits::PrimInt;
+
+fn abs<I: PrimInt>(a: I) -> I {
+    if a < I::zero() {
+        I::zero() - a
+    } else {
+        a
+    }
+}
+
+fn to_i128<I: PrimInt>(a: I) -> i128 {
+    a.to_i128().expect("value doesn't fit into i128")
+}
+
+fn from_i128<I: PrimInt>(a: i128) -> I {
+    I::from(a).unwrap_or_else(|| panic!("{} doesn't fit into {}", a, std::any::type_name::<I>()))
+}
+
+// Always non-negative
+pub fn gcd<I: PrimInt>(a: I, b: I) -> I {
+    let mut a = abs(a);
+    let mut b = abs(b);
+    while b != I::zero() {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+pub fn lcm<I: PrimInt>(a: I, b: I) -> I {
+    if a == I::zero() || b == I::zero() {
+        return I::zero();
+    }
+    abs(a / gcd(a, b) * b)
+}
+
+pub fn gcd_of<I: PrimInt>(numbers: &[I]) -> I {
+    numbers.iter().fold(I::zero(), |acc, &x| gcd(acc, x))
+}
+
+pub fn lcm_of<I: PrimInt>(numbers: &[I]) -> I {
+    numbers.iter().fold(I::one(), |acc, &x| lcm(acc, x))
+}
+
+// Returns (g, x, y) such that a * x + b * y = g = gcd(a, b).
+// Coefficients are usually negative, so a signed type is expected
+pub fn extended_gcd<I: PrimInt>(a: I, b: I) -> (I, I, I) {
+    let (g, x, y) = extended_gcd_i128(to_i128(a), to_i128(b));
+    (from_i128(g), from_i128(x), from_i128(y))
+}
+
+fn extended_gcd_i128(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+// x such that a * x = 1 (mod m), in [0, m) range
+pub fn mod_inverse<I: PrimInt>(a: I, m: I) -> Option<I> {
+    let m = to_i128(m);
+    let (g, x, _) = extended_gcd_i128(to_i128(a).rem_euclid(m), m);
+    if g != 1 {
+        return None;
+    }
+    Some(from_i128(x.rem_euclid(m)))
+}
+
+// Solves x = r (mod m) for every given (r, m) pair. Moduli don't have to be coprime.
+// Returns the smallest non-negative x and the lcm of all the moduli, or None
+// if there's no solution
+pub fn chinese_remainder<I: PrimInt>(congruences: &[(I, I)]) -> Option<(I, I)> {
+    let mut result: i128 = 0;
+    let mut modulus: i128 = 1;
+    for &(r, m) in congruences.iter() {
+        let m = to_i128(m);
+        let r = to_i128(r).rem_euclid(m);
+        // result + modulus * k = r (mod m)
+        let (g, p, _) = extended_gcd_i128(modulus, m);
+        if (r - result) % g != 0 {
+            return None;
+        }
+        let m_g = m / g;
+        let k = ((r - result) / g).rem_euclid(m_g) * p.rem_euclid(m_g) % m_g;
+        result += modulus * k;
+        modulus *= m_g;
+        result = result.rem_euclid(modulus);
+    }
+    Some((from_i128(result), from_i128(modulus)))
+}
+
+pub fn pow_mod<I: PrimInt>(base: I, exp: I, modulus: I) -> I {
+    assert!(exp >= I::zero());
+    let modulus = to_i128(modulus);
+    // u128 is wide enough to multiply any two 64-bit residues
+    let mut base = to_i128(base).rem_euclid(modulus) as u128;
+    let modulus = modulus as u128;
+    let mut exp = to_i128(exp);
+    let mut result = 1 % modulus;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    from_i128(result as i128)
+}
+
+// Turns a dial with positions 0..size (a counter modulo size) by delta clicks,
+// positive delta counts up. Returns the final position and the number of clicks
+// that stopped at zero, the starting position doesn't count. O(1) for any delta
+pub fn dial_turn<I: PrimInt>(pos: I, delta: I, size: I) -> (I, I) {
+    let size = to_i128(size);
+    assert!(size > 0);
+    let pos = to_i128(pos).rem_euclid(size);
+    let delta = to_i128(delta);
+    let end = pos + delta;
+    // multiples of size in (pos, end] going up or in [end, pos) going down
+    let zero_count = if delta >= 0 {
+        end.div_euclid(size)
+    } else {
+        (pos - 1).div_euclid(size) - (end - 1).div_euclid(size)
+    };
+    (from_i128(end.rem_euclid(size)), from_i128(zero_count))
+}
+
+// Number of decimal digits, sign is not counted
+pub fn digit_count<I: PrimInt>(n: I) -> u32 {
+    let ten = I::from(10).unwrap();
+    let mut n = n / ten;
+    let mut result = 1;
+    while n != I::zero() {
+        n = n / ten;
+        result += 1;
+    }
+    result
+}
+
+// Appends decimal digits of b to a: concat(12, 345) = 12345
+pub fn concat<I: PrimInt>(a: I, b: I) -> I {
+    a * I::from(10).unwrap().pow(digit_count(b)) + b
+}
+
+// Fraction-free Gaussian elimination (Bareiss algorithm), so every
+// intermediate value stays an integer
+fn determinant_i128(mut matrix: Vec<Vec<i128>>) -> i128 {
+    let n = matrix.len();
+    if n == 0 {
+        return 1;
+    }
+    let mut sign = 1;
+    let mut prev = 1;
+    for k in 0..n - 1 {
+        if matrix[k][k] == 0 {
+            let Some(pivot) = (k + 1..n).find(|&i| matrix[i][k] != 0) else {
+                return 0;
+            };
+            matrix.swap(k, pivot);
+            sign = -sign;
+        }
+        for i in k + 1..n {
+            for j in k + 1..n {
+                matrix[i][j] = (matrix[i][j] * matrix[k][k] - matrix[i][k] * matrix[k][j]) / prev;
+            }
+        }
+        prev = matrix[k][k];
+    }
+    sign * matrix[n - 1][n - 1]
+}
+
+pub fn determinant<I: PrimInt>(matrix: &[Vec<I>]) -> I {
+    from_i128(determinant_i128(
+        matrix
+            .iter()
+            .map(|row| row.iter().map(|&v| to_i128(v)).collect())
+            .collect(),
+    ))
+}
+
+// Solves matrix * x = rhs (Cramer's rule). Returns None unless the system
+// has exactly one solution and that solution is integer
+pub fn solve_linear_system<I: PrimInt>(matrix: &[Vec<I>], rhs: &[I]) -> Option<Vec<I>> {
+    let n = matrix.len();
+    assert_eq!(rhs.len(), n);
+    assert!(matrix.iter().all(|row| row.len() == n));
+
+    let matrix: Vec<Vec<i128>> = matrix
+        .iter()
+        .map(|row| row.iter().map(|&v| to_i128(v)).collect())
+        .collect();
+    let det = determinant_i128(matrix.clone());
+    if det == 0 {
+        return None;
+    }
+
+    let mut result = Vec::with_capacity(n);
+    for col in 0..n {
+        let mut replaced = matrix.clone();
+        for (row, &value) in replaced.iter_mut().zip(rhs.iter()) {
+            row[col] = to_i128(value);
+        }
+        let numerator = determinant_i128(replaced);
+        if numerator % det != 0 {
+            return None;
+        }
+        result.push(I::from(numerator / det)?);
+    }
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        chinese_remainder, concat, determinant, dial_turn, digit_count, extended_gcd, gcd, gcd_of, lcm, lcm_of,
+        mod_inverse, pow_mod, solve_linear_system,
+    };
+
+    #[test]
+    fn gcd_lcm_test() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(gcd(17u64, 5u64), 1);
+
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(-4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+
+        assert_eq!(gcd_of(&[12, 18, 30]), 6);
+        assert_eq!(lcm_of(&[2u64, 3, 4, 5]), 60);
+        assert_eq!(lcm_of::<u64>(&[]), 1);
+    }
+
+    #[test]
+    fn extended_gcd_test() {
+        let (g, x, y) = extended_gcd(240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+
+        let (g, x, y) = extended_gcd(-35, 15);
+        assert_eq!(g, 5);
+        assert_eq!(-35 * x + 15 * y, 5);
+    }
+
+    #[test]
+    fn mod_inverse_test() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(10u32, 17), Some(12));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+    }
+
+    #[test]
+    fn chinese_remainder_test() {
+        assert_eq!(chinese_remainder(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(chinese_remainder(&[(0u64, 7), (12, 13)]), Some((77, 91)));
+        // non-coprime moduli
+        assert_eq!(chinese_remainder(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(chinese_remainder(&[(1, 4), (2, 6)]), None);
+        assert_eq!(chinese_remainder::<i64>(&[]), Some((0, 1)));
+    }
+
+    #[test]
+    fn pow_mod_test() {
+        assert_eq!(pow_mod(2, 10, 1000), 24);
+        assert_eq!(pow_mod(3u64, 0, 7), 1);
+        assert_eq!(pow_mod(5, 3, 1), 0);
+        assert_eq!(pow_mod(u64::MAX - 1, 2, u64::MAX), 1);
+    }
+
+    #[test]
+    fn dial_turn_test() {
+        assert_eq!(dial_turn(5, -5, 100), (0, 1));
+        assert_eq!(dial_turn(5, -10, 100), (95, 1));
+        assert_eq!(dial_turn(0, -5, 100), (95, 0));
+        assert_eq!(dial_turn(0, -100, 100), (0, 1));
+        assert_eq!(dial_turn(90, 25, 100), (15, 1));
+        assert_eq!(dial_turn(50, 1000, 100), (50, 10));
+        assert_eq!(dial_turn(3, 0, 7), (3, 0));
+        assert_eq!(dial_turn(1, -16, 7), (6, 3));
+        assert_eq!(dial_turn(50i64, 1_000_000_000_000, 100), (50, 10_000_000_000));
+    }
+
+    #[test]
+    fn digits_test() {
+        assert_eq!(digit_count(0), 1);
+        assert_eq!(digit_count(9), 1);
+        assert_eq!(digit_count(10), 2);
+        assert_eq!(digit_count(-123), 3);
+        assert_eq!(digit_count(u64::MAX), 20);
+
+        assert_eq!(concat(15, 6), 156);
+        assert_eq!(concat(123u64, 456), 123456);
+        assert_eq!(concat(12, 0), 120);
+    }
+
+    #[test]
+    fn determinant_test() {
+        assert_eq!(determinant(&[vec![1, 2], vec![3, 4]]), -2);
+        assert_eq!(determinant(&[vec![0, 1, 2], vec![1, 0, 3], vec![4, -3, 8]]), -2);
+        assert_eq!(determinant(&[vec![1, 2], vec![2, 4]]), 0);
+    }
+
+    #[test]
+    fn solve_linear_system_test() {
+        // 94a + 22b = 8400, 34a + 67b = 5400
+        assert_eq!(
+            solve_linear_system(&[vec![94, 22], vec![34, 67]], &[8400, 5400]),
+            Some(vec![80, 40])
+        );
+        // non-integer solution
+        assert_eq!(
+            solve_linear_system(&[vec![26, 67], vec![66, 21]], &[12748, 12176]),
+            None
+        );
+        // singular
+        assert_eq!(solve_linear_system(&[vec![1, 2], vec![2, 4]], &[3, 6]), None);
+        assert_eq!(
+            solve_linear_system(&[vec![2, 1, -1], vec![-3, -1, 2], vec![-2, 1, 2]], &[8i64, -11, -3]),
+            Some(vec![2, 3, -1])
+        );
+    }
+}
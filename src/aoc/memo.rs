@@ -0,0 +1,141 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// A cache for recursive solvers. The compute closure receives the memo
+// itself, so it can recurse into get_or_compute for smaller subproblems:
+//
+// fn fib(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+//     memo.get_or_compute(&n, |memo| if n < 2 { n } else { fib(n - 1, memo) + fib(n - 2, memo) })
+// }
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    hits: usize,
+    misses: usize,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo {
+            cache: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+}
+
+impl<K, V> Memo<K, V>
+where
+    K: Eq + Hash,
+    V: Clone,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Looks the key up by any borrowed form (&str for String keys, &[T] for Vec<T>),
+    // the owned key is only created when the value is not cached yet
+    pub fn get_or_compute<Q, F>(&mut self, key: &Q, compute: F) -> V
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ToOwned<Owned = K> + ?Sized,
+        F: FnOnce(&mut Self) -> V,
+    {
+        if let Some(value) = self.get(key) {
+            return value;
+        }
+        let value = compute(self);
+        self.cache.insert(key.to_owned(), value.clone());
+        value
+    }
+
+    pub fn get<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        match self.cache.get(key) {
+            Some(value) => {
+                self.hits += 1;
+                Some(value.clone())
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    pub fn insert(&mut self, key: K, value: V) {
+        self.cache.insert(key, value);
+    }
+
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    // Drops cached values and resets statistics
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.hits = 0;
+        self.misses = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Memo;
+
+    fn fib(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+        memo.get_or_compute(&n, |memo| if n < 2 { n } else { fib(n - 1, memo) + fib(n - 2, memo) })
+    }
+
+    #[test]
+    fn recursion_test() {
+        let mut memo = Memo::new();
+        assert_eq!(fib(90, &mut memo), 2880067194370816120);
+        assert_eq!(memo.len(), 91);
+        assert_eq!(memo.misses(), 91);
+        assert_eq!(memo.hits(), 88);
+
+        // everything is cached now
+        assert_eq!(fib(90, &mut memo), 2880067194370816120);
+        assert_eq!(memo.misses(), 91);
+        assert_eq!(memo.hits(), 89);
+
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!(memo.hits(), 0);
+        assert_eq!(memo.misses(), 0);
+    }
+
+    #[test]
+    fn borrowed_key_test() {
+        // number of ways to split a string into pieces of length 1 or 2
+        fn count(s: &str, memo: &mut Memo<String, u64>) -> u64 {
+            memo.get_or_compute(s, |memo| match s.len() {
+                0 | 1 => 1,
+                _ => count(&s[1..], memo) + count(&s[2..], memo),
+            })
+        }
+
+        let mut memo = Memo::new();
+        assert_eq!(count("abcdef", &mut memo), 13);
+        assert_eq!(memo.len(), 7);
+        assert_eq!(memo.get("cdef"), Some(5));
+        assert_eq!(memo.get("xyz"), None);
+    }
+}
@@ -0,0 +1,321 @@
+// Nonogram lines: a row of cells, some of them unknown, and the clues, the
+// lengths of the runs of filled cells in order. The line solver is a DP over
+// (position, clues used) states, which both counts the arrangements and tells
+// which cells are the same in all of them. The grid solver runs it over rows
+// and columns until nothing changes and guesses when it gets stuck.
+
+use crate::Field;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Cell {
+    #[default]
+    Unknown,
+    Empty,
+    Filled,
+}
+
+impl Cell {
+    // '?', '.' and '#' as in puzzle inputs
+    pub fn from_char(ch: char) -> Option<Cell> {
+        match ch {
+            '?' => Some(Cell::Unknown),
+            '.' => Some(Cell::Empty),
+            '#' => Some(Cell::Filled),
+            _ => None,
+        }
+    }
+
+    pub fn to_char(self) -> char {
+        match self {
+            Cell::Unknown => '?',
+            Cell::Empty => '.',
+            Cell::Filled => '#',
+        }
+    }
+}
+
+// Panics on anything but '?', '.' and '#'
+pub fn parse_line(line: &str) -> Vec<Cell> {
+    line.chars()
+        .map(|ch| Cell::from_char(ch).unwrap_or_else(|| panic!("Unexpected cell '{}'", ch)))
+        .collect()
+}
+
+// Lengths of the runs of filled cells, the clues the line is a solution for.
+// None if some cell is still unknown
+pub fn clues_of(line: &[Cell]) -> Option<Vec<usize>> {
+    let mut result = Vec::new();
+    let mut run = 0;
+    for cell in line.iter() {
+        match cell {
+            Cell::Unknown => return None,
+            Cell::Filled => run += 1,
+            Cell::Empty => {
+                if run > 0 {
+                    result.push(run);
+                    run = 0;
+                }
+            }
+        }
+    }
+    if run > 0 {
+        result.push(run);
+    }
+    Some(result)
+}
+
+// Position after the run of the given length placed at start and the empty
+// cell that follows it, None if the run doesn't fit there
+fn place_run(line: &[Cell], start: usize, len: usize) -> Option<usize> {
+    let end = start + len;
+    if end > line.len() || line[start..end].contains(&Cell::Empty) || line.get(end) == Some(&Cell::Filled) {
+        return None;
+    }
+    Some((end + 1).min(line.len()))
+}
+
+// Both tables are indexed by [position][clues used]. A state is a position
+// where the next run may start: the beginning of the line or right after
+// the empty cell that closes a run
+struct LineDp {
+    // arrangements of line[..i] that use clues[..j] and end in a state
+    prefix: Vec<Vec<u64>>,
+    // arrangements of line[i..] with clues[j..]
+    suffix: Vec<Vec<u64>>,
+}
+
+impl LineDp {
+    fn new(line: &[Cell], clues: &[usize]) -> Self {
+        let (n, m) = (line.len(), clues.len());
+        let mut prefix = vec![vec![0; m + 1]; n + 1];
+        prefix[0][0] = 1;
+        for i in 0..n {
+            for j in 0..=m {
+                let ways = prefix[i][j];
+                if ways == 0 {
+                    continue;
+                }
+                if line[i] != Cell::Filled {
+                    prefix[i + 1][j] += ways;
+                }
+                if line[i] != Cell::Empty && j < m {
+                    if let Some(next) = place_run(line, i, clues[j]) {
+                        prefix[next][j + 1] += ways;
+                    }
+                }
+            }
+        }
+
+        let mut suffix = vec![vec![0; m + 1]; n + 1];
+        suffix[n][m] = 1;
+        for i in (0..n).rev() {
+            for j in 0..=m {
+                let mut ways = 0;
+                if line[i] != Cell::Filled {
+                    ways += suffix[i + 1][j];
+                }
+                if line[i] != Cell::Empty && j < m {
+                    if let Some(next) = place_run(line, i, clues[j]) {
+                        ways += suffix[next][j + 1];
+                    }
+                }
+                suffix[i][j] = ways;
+            }
+        }
+
+        LineDp { prefix, suffix }
+    }
+
+    fn count(&self) -> u64 {
+        self.suffix[0][0]
+    }
+}
+
+// Number of ways to fill the unknown cells so that the runs match the clues
+pub fn count_arrangements(line: &[Cell], clues: &[usize]) -> u64 {
+    LineDp::new(line, clues).count()
+}
+
+// The line with every cell that is the same in all the arrangements filled in,
+// None if there are no arrangements at all
+pub fn solve_line(line: &[Cell], clues: &[usize]) -> Option<Vec<Cell>> {
+    let dp = LineDp::new(line, clues);
+    let total = dp.count();
+    if total == 0 {
+        return None;
+    }
+
+    // number of arrangements where the cell is empty and where it's filled,
+    // runs add to a whole range of cells, so the filled ones go through a
+    // difference array
+    let n = line.len();
+    let mut empty = vec![0u64; n];
+    let mut filled_diff = vec![0i128; n + 1];
+    for i in 0..n {
+        for (j, &clue) in clues.iter().enumerate().chain(std::iter::once((clues.len(), &0))) {
+            let before = dp.prefix[i][j];
+            if before == 0 {
+                continue;
+            }
+            if line[i] != Cell::Filled {
+                empty[i] += before * dp.suffix[i + 1][j];
+            }
+            if line[i] == Cell::Empty || j == clues.len() {
+                continue;
+            }
+            let Some(next) = place_run(line, i, clue) else {
+                continue;
+            };
+            let ways = before * dp.suffix[next][j + 1];
+            filled_diff[i] += ways as i128;
+            filled_diff[i + clue] -= ways as i128;
+            if i + clue < n {
+                empty[i + clue] += ways;
+            }
+        }
+    }
+
+    let mut result = line.to_vec();
+    let mut filled = 0i128;
+    for (i, cell) in result.iter_mut().enumerate() {
+        filled += filled_diff[i];
+        if filled as u64 == total {
+            *cell = Cell::Filled;
+        } else if empty[i] == total {
+            *cell = Cell::Empty;
+        }
+    }
+    Some(result)
+}
+
+// Solves the whole grid given the clues for every row and every column.
+// Returns the first solution found, None if there is none
+pub fn solve(row_clues: &[Vec<usize>], col_clues: &[Vec<usize>]) -> Option<Field<Cell>> {
+    let field = Field::with_size(row_clues.len(), col_clues.len());
+    solve_from(field, row_clues, col_clues)
+}
+
+fn solve_from(mut field: Field<Cell>, row_clues: &[Vec<usize>], col_clues: &[Vec<usize>]) -> Option<Field<Cell>> {
+    propagate(&mut field, row_clues, col_clues)?;
+
+    let (row_count, col_count) = (field.get_row_count(), field.get_col_count());
+    let unknown = (0..row_count * col_count)
+        .map(|i| (i / col_count, i % col_count))
+        .find(|&(row, col)| *field.get(row, col) == Cell::Unknown);
+    let Some((row, col)) = unknown else {
+        return Some(field);
+    };
+
+    for guess in [Cell::Filled, Cell::Empty] {
+        let mut attempt = field.clone();
+        *attempt.get_mut(row, col) = guess;
+        if let Some(solution) = solve_from(attempt, row_clues, col_clues) {
+            return Some(solution);
+        }
+    }
+    None
+}
+
+// Runs the line solver over the rows and the columns until no more cells are
+// deduced. None if some line has no arrangements
+fn propagate(field: &mut Field<Cell>, row_clues: &[Vec<usize>], col_clues: &[Vec<usize>]) -> Option<()> {
+    let (row_count, col_count) = (field.get_row_count(), field.get_col_count());
+    let mut dirty_rows = vec![true; row_count];
+    let mut dirty_cols = vec![true; col_count];
+    while dirty_rows.contains(&true) || dirty_cols.contains(&true) {
+        for row in 0..row_count {
+            if !std::mem::take(&mut dirty_rows[row]) {
+                continue;
+            }
+            let line: Vec<Cell> = (0..col_count).map(|col| *field.get(row, col)).collect();
+            let solved = solve_line(&line, &row_clues[row])?;
+            for col in 0..col_count {
+                if solved[col] != line[col] {
+                    *field.get_mut(row, col) = solved[col];
+                    dirty_cols[col] = true;
+                }
+            }
+        }
+        for col in 0..col_count {
+            if !std::mem::take(&mut dirty_cols[col]) {
+                continue;
+            }
+            let line: Vec<Cell> = (0..row_count).map(|row| *field.get(row, col)).collect();
+            let solved = solve_line(&line, &col_clues[col])?;
+            for row in 0..row_count {
+                if solved[row] != line[row] {
+                    *field.get_mut(row, col) = solved[row];
+                    dirty_rows[row] = true;
+                }
+            }
+        }
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{clues_of, count_arrangements, parse_line, solve, solve_line, Cell};
+
+    fn to_string(line: &[Cell]) -> String {
+        line.iter().map(|c| c.to_char()).collect()
+    }
+
+    #[test]
+    fn clues_of_test() {
+        assert_eq!(clues_of(&parse_line("...###")), Some(vec![3]));
+        assert_eq!(clues_of(&parse_line("#.#.###")), Some(vec![1, 1, 3]));
+        assert_eq!(clues_of(&parse_line(".#.###.#.######")), Some(vec![1, 3, 1, 6]));
+        assert_eq!(clues_of(&parse_line("####.#...#...")), Some(vec![4, 1, 1]));
+        assert_eq!(clues_of(&parse_line("#....######..#####.")), Some(vec![1, 6, 5]));
+        assert_eq!(clues_of(&parse_line("....")), Some(vec![]));
+        assert_eq!(clues_of(&parse_line("##.#?##")), None);
+    }
+
+    #[test]
+    fn count_arrangements_test() {
+        assert_eq!(count_arrangements(&parse_line("???.###"), &[1, 1, 3]), 1);
+        assert_eq!(count_arrangements(&parse_line(".??..??...?##."), &[1, 1, 3]), 4);
+        assert_eq!(count_arrangements(&parse_line("?###????????"), &[3, 2, 1]), 10);
+        assert_eq!(count_arrangements(&parse_line("?????"), &[]), 1);
+        assert_eq!(count_arrangements(&parse_line("??#??"), &[]), 0);
+        assert_eq!(count_arrangements(&parse_line(""), &[]), 1);
+        assert_eq!(count_arrangements(&parse_line("???"), &[2, 1]), 0);
+    }
+
+    #[test]
+    fn solve_line_test() {
+        let solve = |line: &str, clues: &[usize]| solve_line(&parse_line(line), clues).map(|l| to_string(&l));
+        assert_eq!(solve("??????????", &[8]).unwrap(), "??######??");
+        assert_eq!(solve("???.###", &[1, 1, 3]).unwrap(), "#.#.###");
+        assert_eq!(solve("?????", &[]).unwrap(), ".....");
+        assert_eq!(solve("????#", &[1, 1]).unwrap(), "???.#");
+        assert_eq!(solve("?????", &[3, 1]).unwrap(), "###.#");
+        assert_eq!(solve("#????", &[5]).unwrap(), "#####");
+        assert_eq!(solve("??#??", &[]), None);
+    }
+
+    #[test]
+    fn solve_test() {
+        // a 5x5 letter P
+        let rows = vec![vec![4], vec![1, 1], vec![4], vec![1], vec![1]];
+        let cols = vec![vec![5], vec![1, 1], vec![1, 1], vec![3]];
+        let solution = solve(&rows, &cols).unwrap();
+        let text: Vec<String> = (0..5)
+            .map(|row| (0..4).map(|col| solution.get(row, col).to_char()).collect())
+            .collect();
+        assert_eq!(text, vec!["####", "#..#", "####", "#...", "#..."]);
+
+        for (row, clues) in rows.iter().enumerate() {
+            let line: Vec<Cell> = (0..4).map(|col| *solution.get(row, col)).collect();
+            assert_eq!(clues_of(&line).as_ref(), Some(clues));
+        }
+
+        // both diagonals fit, the first guess wins
+        let solution = solve(&[vec![1], vec![1]], &[vec![1], vec![1]]).unwrap();
+        assert_eq!(*solution.get(0, 0), Cell::Filled);
+        assert_eq!(*solution.get(0, 1), Cell::Empty);
+
+        assert!(solve(&[vec![2]], &[vec![1], vec![]]).is_none());
+    }
+}
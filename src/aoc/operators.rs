@@ -0,0 +1,151 @@
+// "Insert operators between the numbers to get the target" puzzles. Operators
+// are evaluated strictly left to right, without precedence. The search goes from
+// the last operand backwards, undoing one operator at a time with its inverse,
+// which cuts most of the branches right away: a product has to be divisible by
+// the last operand, a concatenation has to end with its digits and so on.
+//
+// Works on non-negative numbers, operands are expected to be positive.
+
+use crate::math::digit_count;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Operator {
+    pub symbol: &'static str,
+    // None on overflow
+    pub apply: fn(u64, u64) -> Option<u64>,
+    // The left operand that gives the result with the given right one:
+    // apply(inverse(result, right)?, right) == Some(result)
+    pub inverse: fn(u64, u64) -> Option<u64>,
+}
+
+pub const ADD: Operator = Operator {
+    symbol: "+",
+    apply: |a, b| a.checked_add(b),
+    inverse: |result, b| result.checked_sub(b),
+};
+
+pub const MUL: Operator = Operator {
+    symbol: "*",
+    apply: |a, b| a.checked_mul(b),
+    inverse: |result, b| {
+        if b == 0 || result % b != 0 {
+            return None;
+        }
+        Some(result / b)
+    },
+};
+
+// 12 || 345 = 12345
+pub const CONCAT: Operator = Operator {
+    symbol: "||",
+    apply: |a, b| a.checked_mul(10u64.checked_pow(digit_count(b))?)?.checked_add(b),
+    inverse: |result, b| {
+        let shift = 10u64.checked_pow(digit_count(b))?;
+        if result % shift != b {
+            return None;
+        }
+        Some(result / shift)
+    },
+};
+
+// Operators to put between the operands to get the target, None if there's no
+// such combination. When several combinations work, operators listed earlier
+// are preferred for the rightmost positions
+pub fn find_operators(operands: &[u64], target: u64, operators: &[Operator]) -> Option<Vec<Operator>> {
+    if operands.is_empty() {
+        return None;
+    }
+    let mut result = Vec::with_capacity(operands.len() - 1);
+    if search(operands, target, operators, &mut result) {
+        Some(result)
+    } else {
+        None
+    }
+}
+
+fn search(operands: &[u64], target: u64, operators: &[Operator], chosen: &mut Vec<Operator>) -> bool {
+    let (&last, rest) = operands.split_last().unwrap();
+    if rest.is_empty() {
+        return last == target;
+    }
+    for operator in operators.iter() {
+        let Some(left) = (operator.inverse)(target, last) else {
+            continue;
+        };
+        if search(rest, left, operators, chosen) {
+            chosen.push(*operator);
+            return true;
+        }
+    }
+    false
+}
+
+pub fn is_solvable(operands: &[u64], target: u64, operators: &[Operator]) -> bool {
+    find_operators(operands, target, operators).is_some()
+}
+
+// Left to right evaluation, None on overflow
+pub fn evaluate(operands: &[u64], operators: &[Operator]) -> Option<u64> {
+    assert_eq!(operands.len(), operators.len() + 1);
+    let mut result = operands[0];
+    for (operator, operand) in operators.iter().zip(operands[1..].iter()) {
+        result = (operator.apply)(result, *operand)?;
+    }
+    Some(result)
+}
+
+// "81 + 40 * 27"
+pub fn expression(operands: &[u64], operators: &[Operator]) -> String {
+    let mut result = operands.first().map(|o| o.to_string()).unwrap_or_default();
+    for (operator, operand) in operators.iter().zip(operands.iter().skip(1)) {
+        result.push_str(&format!(" {} {}", operator.symbol, operand));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{evaluate, expression, find_operators, is_solvable, Operator, ADD, CONCAT, MUL};
+
+    fn symbols(operators: &[Operator]) -> Vec<&'static str> {
+        operators.iter().map(|o| o.symbol).collect()
+    }
+
+    #[test]
+    fn find_operators_test() {
+        let operators = [ADD, MUL];
+        let found = find_operators(&[81, 40, 27], 3267, &operators).unwrap();
+        assert_eq!(evaluate(&[81, 40, 27], &found), Some(3267));
+        // 81 + 40 * 27 works too, but + is preferred for the last operator
+        assert_eq!(symbols(&found), vec!["*", "+"]);
+
+        assert_eq!(
+            symbols(&find_operators(&[11, 6, 16, 20], 292, &operators).unwrap()),
+            vec!["+", "*", "+"]
+        );
+        assert!(!is_solvable(&[17, 5], 83, &operators));
+        assert!(is_solvable(&[5], 5, &operators));
+        assert!(!is_solvable(&[], 0, &operators));
+    }
+
+    #[test]
+    fn concat_test() {
+        assert_eq!((CONCAT.apply)(12, 345), Some(12345));
+        assert_eq!((CONCAT.inverse)(12345, 345), Some(12));
+        assert_eq!((CONCAT.inverse)(12345, 45), Some(123));
+        assert_eq!((CONCAT.inverse)(12345, 44), None);
+        assert_eq!((CONCAT.apply)(u64::MAX / 10, 12), None);
+
+        let operands = [6, 8, 6, 15];
+        assert!(!is_solvable(&operands, 7290, &[ADD, MUL]));
+        let found = find_operators(&operands, 7290, &[ADD, MUL, CONCAT]).unwrap();
+        assert_eq!(expression(&operands, &found), "6 * 8 || 6 * 15");
+    }
+
+    #[test]
+    fn overflow_test() {
+        assert_eq!(evaluate(&[u64::MAX, 2], &[MUL]), None);
+        assert_eq!((MUL.inverse)(10, 0), None);
+        assert_eq!((ADD.inverse)(3, 5), None);
+    }
+}
@@ -0,0 +1,287 @@
+// Small building blocks for puzzle input parsing. Every error carries
+// 1-based line and column of the place that failed to parse. Functions that
+// work on a single line report line 1, use lines() or ParseError::at_line
+// to put the actual line number in.
+
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub col: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(col: usize, message: impl Into<String>) -> Self {
+        ParseError {
+            line: 1,
+            col,
+            message: message.into(),
+        }
+    }
+
+    pub fn at_line(self, line: usize) -> Self {
+        ParseError { line, ..self }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, col {}: {}", self.line, self.col, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub type Result<T> = std::result::Result<T, ParseError>;
+
+// 1-based column of a substring within the line it was sliced from, None if
+// the part lies outside of the line
+fn column_of(line: &str, part: &str) -> Option<usize> {
+    let offset = (part.as_ptr() as usize).checked_sub(line.as_ptr() as usize)?;
+    if offset + part.len() > line.len() {
+        return None;
+    }
+    Some(line[..offset].chars().count() + 1)
+}
+
+// Parses a substring of the line, reporting its position on failure. Errors
+// point at column 1 when the part wasn't sliced from the line
+pub fn value<T: FromStr>(line: &str, part: &str) -> Result<T> {
+    let trimmed = part.trim();
+    trimmed.parse::<T>().map_err(|_| {
+        ParseError::new(
+            column_of(line, trimmed).unwrap_or(1),
+            format!("failed to convert \"{}\" to {}", trimmed, std::any::type_name::<T>()),
+        )
+    })
+}
+
+// aoc::input::ints that returns an error at the column of a number that
+// doesn't fit into T instead of panicking. Puzzles use aoc::input::ints
+pub fn ints<T: FromStr>(line: &str) -> Result<Vec<T>> {
+    crate::input::int_tokens(line).map(|token| value(line, token)).collect()
+}
+
+// Splits the text into blocks separated by one or more blank lines, see
+// aoc::input::paragraphs for the lazy version
+pub fn blocks(text: &str) -> Vec<&str> {
+    crate::input::paragraphs(text).collect()
+}
+
+// Applies the parser to every line of the text, errors get the line number
+pub fn lines<T, F>(text: &str, mut parser: F) -> Result<Vec<T>>
+where
+    F: FnMut(&str) -> Result<T>,
+{
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| parser(line).map_err(|e| e.at_line(i + 1)))
+        .collect()
+}
+
+// "Card 1: 41 48 83" -> ("Card 1", "41 48 83"), both parts are trimmed
+pub fn key_value(line: &str) -> Result<(&str, &str)> {
+    match line.split_once(':') {
+        Some((key, values)) => Ok((key.trim(), values.trim())),
+        None => Err(ParseError::new(line.chars().count() + 1, "expected `:`")),
+    }
+}
+
+// "Time:  7  15   30" -> ("Time", [7, 15, 30])
+pub fn key_values<T: FromStr>(line: &str) -> Result<(&str, Vec<T>)> {
+    let (key, values) = key_value(line)?;
+    let values = values
+        .split_ascii_whitespace()
+        .map(|v| value(line, v))
+        .collect::<Result<Vec<T>>>()?;
+    Ok((key, values))
+}
+
+// "3,4" -> (3, 4) for the ',' separator
+pub fn pair<T: FromStr>(line: &str, separator: char) -> Result<(T, T)> {
+    match line.split_once(separator) {
+        Some((a, b)) => Ok((value(line, a)?, value(line, b)?)),
+        None => Err(ParseError::new(
+            line.chars().count() + 1,
+            format!("expected `{}`", separator),
+        )),
+    }
+}
+
+// Matches the line against a pattern where every {} placeholder captures the
+// text up to the next literal part of the pattern (or up to the line end)
+//
+// scan("AAA = (BBB, CCC)", "{} = ({}, {})") == Ok(vec!["AAA", "BBB", "CCC"])
+pub fn scan<'a>(line: &'a str, pattern: &str) -> Result<Vec<&'a str>> {
+    let mut literals = pattern.split("{}").peekable();
+    let mut result = Vec::new();
+
+    let prefix = literals.next().unwrap_or_default();
+    let Some(mut rest) = line.strip_prefix(prefix) else {
+        return Err(ParseError::new(1, format!("expected `{}`", prefix)));
+    };
+
+    while let Some(literal) = literals.next() {
+        let captured = if literal.is_empty() {
+            if literals.peek().is_some() {
+                // nothing tells where the first of two adjacent captures ends
+                return Err(ParseError::new(1, format!("adjacent placeholders in `{}`", pattern)));
+            }
+            // the last placeholder takes everything that is left
+            let captured = rest;
+            rest = &rest[rest.len()..];
+            captured
+        } else {
+            let Some(pos) = rest.find(literal) else {
+                return Err(ParseError::new(
+                    column_of(line, rest).unwrap_or(1),
+                    format!("expected `{}`", literal),
+                ));
+            };
+            let captured = &rest[..pos];
+            rest = &rest[pos + literal.len()..];
+            captured
+        };
+        result.push(captured);
+    }
+
+    if !rest.is_empty() {
+        return Err(ParseError::new(
+            column_of(line, rest).unwrap_or(1),
+            "unexpected trailing characters",
+        ));
+    }
+    Ok(result)
+}
+
+// Typed version of scan, converts every capture to the given type and
+// returns them as a tuple:
+//
+// let (x, y): (i32, i32) = aoc::scan!(line, "Prize: X={}, Y={}", i32, i32)?;
+#[macro_export]
+macro_rules! scan {
+    ($line:expr, $pattern:expr, $($t:ty),+ $(,)?) => {
+        (|| -> $crate::parse::Result<($($t,)+)> {
+            let line: &str = $line;
+            let captures = $crate::parse::scan(line, $pattern)?;
+            let expected = [$(stringify!($t)),+].len();
+            if captures.len() != expected {
+                return Err($crate::parse::ParseError::new(
+                    1,
+                    format!("pattern has {} placeholders, {} types given", captures.len(), expected),
+                ));
+            }
+            let mut captures = captures.into_iter();
+            Ok(($($crate::parse::value::<$t>(line, captures.next().unwrap())?,)+))
+        })()
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{blocks, column_of, ints, key_value, key_values, lines, pair, scan, value, ParseError};
+
+    #[test]
+    fn ints_test() {
+        assert_eq!(ints::<i32>("p=0,4 v=3,-3").unwrap(), vec![0, 4, 3, -3]);
+        assert_eq!(ints::<i64>("Button A: X+94, Y+34").unwrap(), vec![94, 34]);
+        assert_eq!(ints::<u32>("10-20").unwrap(), vec![10, 20]);
+        assert_eq!(ints::<i32>("-5 - 3 -").unwrap(), vec![-5, 3]);
+        assert!(ints::<i32>("no numbers").unwrap().is_empty());
+
+        let err = ints::<u8>("1 2 300").unwrap_err();
+        assert_eq!((err.line, err.col), (1, 5));
+    }
+
+    #[test]
+    fn value_test() {
+        let line = "a:   xyz";
+        assert_eq!(value::<u8>(line, &line[2..]).unwrap_err().col, 6);
+        assert_eq!(value::<u8>(line, "xyz").unwrap_err().col, 1);
+        assert_eq!(value::<u8>("  42 ", "  42 ").unwrap(), 42);
+
+        assert_eq!(column_of(line, &line[5..]), Some(6));
+        assert_eq!(column_of(line, &line[8..]), Some(9));
+        assert_eq!(column_of(&line[2..], line), None);
+        assert_eq!(column_of(&line[..4], &line[2..]), None);
+    }
+
+    #[test]
+    fn blocks_test() {
+        let text = "a\nb\n\n\nc\r\n\r\nd\ne\n";
+        assert_eq!(blocks(text), vec!["a\nb", "c", "d\ne"]);
+        assert!(blocks("\n\n").is_empty());
+    }
+
+    #[test]
+    fn key_values_test() {
+        assert_eq!(key_value("Card   1: 41 48 | 83").unwrap(), ("Card   1", "41 48 | 83"));
+        assert_eq!(
+            key_values::<u64>("Time:  7  15   30").unwrap(),
+            ("Time", vec![7, 15, 30])
+        );
+
+        let err = key_values::<u64>("Time: 7 x").unwrap_err();
+        assert_eq!(err.col, 9);
+        let err = key_value("Time").unwrap_err();
+        assert_eq!(err.col, 5);
+    }
+
+    #[test]
+    fn pair_test() {
+        assert_eq!(pair::<i32>("3,4", ',').unwrap(), (3, 4));
+        assert_eq!(pair::<u64>("47|53", '|').unwrap(), (47, 53));
+        assert_eq!(pair::<i32>("3,x", ',').unwrap_err().col, 3);
+        assert_eq!(pair::<i32>("34", ',').unwrap_err().col, 3);
+    }
+
+    #[test]
+    fn scan_test() {
+        assert_eq!(
+            scan("AAA = (BBB, CCC)", "{} = ({}, {})").unwrap(),
+            vec!["AAA", "BBB", "CCC"]
+        );
+        assert_eq!(scan("move 1 from 2", "move {} from {}").unwrap(), vec!["1", "2"]);
+
+        // the column is where the search for the missing literal has started
+        let err = scan("AAA = (BBB; CCC)", "{} = ({}, {})").unwrap_err();
+        assert_eq!(err.col, 8);
+        assert_eq!(scan("AAA", "BBB").unwrap_err().col, 1);
+        let err = scan("AAA = (BBB, CCC)!", "{} = ({}, {})").unwrap_err();
+        assert_eq!(err.col, 17);
+        assert!(scan("abx", "{}{}x").is_err());
+        assert_eq!(scan("a = ", "{} = {}").unwrap(), vec!["a", ""]);
+    }
+
+    #[test]
+    fn scan_macro_test() {
+        let line = "Button A: X+94, Y+34";
+        assert_eq!(crate::scan!(line, "Button A: X+{}, Y+{}", i32, u8).unwrap(), (94, 34));
+        assert_eq!(crate::scan!("x", "{}", String).unwrap(), ("x".to_owned(),));
+
+        let err = crate::scan!(line, "Button A: X+{}, Y+{}", i32, bool).unwrap_err();
+        assert_eq!(err.col, 19);
+    }
+
+    #[test]
+    fn lines_test() {
+        let text = "1,2\n3,4\n5;6";
+        let err = lines(text, |l| pair::<i32>(l, ',')).unwrap_err();
+        assert_eq!(
+            err,
+            ParseError {
+                line: 3,
+                col: 4,
+                message: "expected `,`".to_owned()
+            }
+        );
+        assert_eq!(err.to_string(), "line 3, col 4: expected `,`");
+        assert_eq!(
+            lines("1,2\n3,4", |l| pair::<i32>(l, ',')).unwrap(),
+            vec![(1, 2), (3, 4)]
+        );
+    }
+}
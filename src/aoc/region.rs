@@ -0,0 +1,125 @@
+use crate::Point;
+
+// A connected set of field nodes along with its shape parameters.
+// Bounding box corners are inclusive
+#[derive(Debug, Default, Clone)]
+pub struct Region {
+    pub points: Vec<Point<i32>>,
+    pub perimeter: usize,
+    pub sides_count: usize,
+    pub min: Point<i32>,
+    pub max: Point<i32>,
+}
+
+// Each corner of a node is described by two orthogonal neighbors
+// and a diagonal one between them
+static CORNERS: [[(i32, i32); 3]; 4] = [
+    [(0, -1), (-1, -1), (-1, 0)],
+    [(-1, 0), (-1, 1), (0, 1)],
+    [(0, 1), (1, 1), (1, 0)],
+    [(1, 0), (1, -1), (0, -1)],
+];
+
+impl Region {
+    pub(crate) fn from_points<F>(points: Vec<Point<i32>>, is_member: F) -> Self
+    where
+        F: Fn(&Point<i32>) -> bool,
+    {
+        assert!(!points.is_empty());
+
+        let mut min = points[0];
+        let mut max = points[0];
+        let mut perimeter = 0;
+        let mut sides_count = 0;
+
+        for point in points.iter() {
+            min.row = min.row.min(point.row);
+            min.col = min.col.min(point.col);
+            max.row = max.row.max(point.row);
+            max.col = max.col.max(point.col);
+
+            perimeter += point.neighbors_orthogonal().filter(|n| !is_member(n)).count();
+
+            // number of sides of a polygon equals to the number of its corners
+            for corner in CORNERS.iter() {
+                let [first, diagonal, second] = corner.map(|(drow, dcol)| {
+                    is_member(&Point {
+                        row: point.row + drow,
+                        col: point.col + dcol,
+                    })
+                });
+                let is_convex = !first && !second;
+                let is_concave = first && second && !diagonal;
+                if is_convex || is_concave {
+                    sides_count += 1;
+                }
+            }
+        }
+
+        Region {
+            points,
+            perimeter,
+            sides_count,
+            min,
+            max,
+        }
+    }
+
+    pub fn area(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.col - self.min.col + 1) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.row - self.min.row + 1) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::Region;
+    use crate::Point;
+
+    fn make_region(points: &[(i32, i32)]) -> Region {
+        let points: Vec<Point<i32>> = points.iter().map(|&(row, col)| Point { row, col }).collect();
+        let set: HashSet<Point<i32>> = points.iter().copied().collect();
+        Region::from_points(points, |p| set.contains(p))
+    }
+
+    #[test]
+    fn single_node_test() {
+        let region = make_region(&[(3, 4)]);
+        assert_eq!(region.area(), 1);
+        assert_eq!(region.perimeter, 4);
+        assert_eq!(region.sides_count, 4);
+        assert_eq!(region.min, Point { row: 3, col: 4 });
+        assert_eq!(region.max, Point { row: 3, col: 4 });
+    }
+
+    #[test]
+    fn l_shape_test() {
+        // X.
+        // XX
+        let region = make_region(&[(0, 0), (1, 0), (1, 1)]);
+        assert_eq!(region.area(), 3);
+        assert_eq!(region.perimeter, 8);
+        assert_eq!(region.sides_count, 6);
+        assert_eq!(region.width(), 2);
+        assert_eq!(region.height(), 2);
+    }
+
+    #[test]
+    fn diagonal_touch_test() {
+        // X.
+        // .X
+        // diagonal nodes are not connected, but the corners are still counted separately
+        let region = make_region(&[(0, 0), (1, 1)]);
+        assert_eq!(region.perimeter, 8);
+        assert_eq!(region.sides_count, 8);
+    }
+}
@@ -0,0 +1,443 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+use num_traits::Zero;
+
+const NO_PARENT: usize = usize::MAX;
+
+// Stores every discovered state once and refers to it by index, so
+// parent links and the heap don't need to clone states around
+struct Arena<S> {
+    states: Vec<S>,
+    parents: Vec<usize>,
+    indices: HashMap<S, usize>,
+}
+
+impl<S> Arena<S>
+where
+    S: Clone + Eq + Hash,
+{
+    fn new() -> Self {
+        Arena {
+            states: Vec::new(),
+            parents: Vec::new(),
+            indices: HashMap::new(),
+        }
+    }
+
+    // Returns the index of the state and whether it was just inserted
+    fn insert(&mut self, state: S, parent: usize) -> (usize, bool) {
+        if let Some(&idx) = self.indices.get(&state) {
+            return (idx, false);
+        }
+        let idx = self.states.len();
+        self.indices.insert(state.clone(), idx);
+        self.states.push(state);
+        self.parents.push(parent);
+        (idx, true)
+    }
+
+    fn path(&self, idx: usize) -> Vec<S> {
+        let mut result = Vec::new();
+        let mut idx = idx;
+        while idx != NO_PARENT {
+            result.push(self.states[idx].clone());
+            idx = self.parents[idx];
+        }
+        result.reverse();
+        result
+    }
+}
+
+// Breadth-first search over an unweighted graph. Returns the path from one of the
+// start states to the closest goal state (both ends included) and its length in steps
+pub fn bfs<S, IS, FN, IN, FG>(starts: IS, mut successors: FN, mut is_goal: FG) -> Option<(Vec<S>, usize)>
+where
+    S: Clone + Eq + Hash,
+    IS: IntoIterator<Item = S>,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = S>,
+    FG: FnMut(&S) -> bool,
+{
+    let mut arena = Arena::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        let (idx, inserted) = arena.insert(start, NO_PARENT);
+        if inserted {
+            queue.push_back(idx);
+        }
+    }
+
+    while let Some(idx) = queue.pop_front() {
+        if is_goal(&arena.states[idx]) {
+            let path = arena.path(idx);
+            let steps = path.len() - 1;
+            return Some((path, steps));
+        }
+        for next in successors(&arena.states[idx]) {
+            let (next_idx, inserted) = arena.insert(next, idx);
+            if inserted {
+                queue.push_back(next_idx);
+            }
+        }
+    }
+
+    None
+}
+
+// All states reachable from the start states, including the start states themselves
+pub fn reachable<S, IS, FN, IN>(starts: IS, mut successors: FN) -> HashSet<S>
+where
+    S: Clone + Eq + Hash,
+    IS: IntoIterator<Item = S>,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = S>,
+{
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if visited.insert(start.clone()) {
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(state) = queue.pop_front() {
+        for next in successors(&state) {
+            if visited.insert(next.clone()) {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    visited
+}
+
+// Cheapest path from any of the start states to a goal state over
+// non-negative edge costs
+pub fn dijkstra<S, C, IS, FN, IN, FG>(starts: IS, successors: FN, is_goal: FG) -> Option<(Vec<S>, C)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    IS: IntoIterator<Item = S>,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+    FG: FnMut(&S) -> bool,
+{
+    astar(starts, successors, |_| C::zero(), is_goal)
+}
+
+// Same as dijkstra but guided by a heuristic. The heuristic must never
+// overestimate the remaining cost for the result to be optimal
+pub fn astar<S, C, IS, FN, IN, FH, FG>(
+    starts: IS,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut is_goal: FG,
+) -> Option<(Vec<S>, C)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    IS: IntoIterator<Item = S>,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+    FH: FnMut(&S) -> C,
+    FG: FnMut(&S) -> bool,
+{
+    let mut arena = Arena::new();
+    let mut costs: Vec<C> = Vec::new();
+    let mut heap = BinaryHeap::new();
+
+    for start in starts {
+        let h = heuristic(&start);
+        let (idx, inserted) = arena.insert(start, NO_PARENT);
+        if inserted {
+            costs.push(C::zero());
+            heap.push(Reverse((h, C::zero(), idx)));
+        }
+    }
+
+    while let Some(Reverse((_, cost, idx))) = heap.pop() {
+        // a cheaper way to this state was found after this entry was queued
+        if cost > costs[idx] {
+            continue;
+        }
+        if is_goal(&arena.states[idx]) {
+            return Some((arena.path(idx), cost));
+        }
+        for (next, step_cost) in successors(&arena.states[idx]) {
+            let new_cost = cost + step_cost;
+            let h = heuristic(&next);
+            let (next_idx, inserted) = arena.insert(next, idx);
+            if inserted {
+                costs.push(new_cost);
+            } else if new_cost < costs[next_idx] {
+                costs[next_idx] = new_cost;
+                arena.parents[next_idx] = idx;
+            } else {
+                continue;
+            }
+            heap.push(Reverse((new_cost + h, new_cost, next_idx)));
+        }
+    }
+
+    None
+}
+
+// Every cheapest path from the start states to the goal states. All goal states
+// reachable at the minimal cost are included
+pub fn all_shortest_paths<S, C, IS, FN, IN, FG>(
+    starts: IS,
+    mut successors: FN,
+    mut is_goal: FG,
+) -> Option<(Vec<Vec<S>>, C)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    IS: IntoIterator<Item = S>,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+    FG: FnMut(&S) -> bool,
+{
+    let mut arena = Arena::new();
+    let mut costs: Vec<C> = Vec::new();
+    let mut predecessors: Vec<Vec<usize>> = Vec::new();
+    let mut heap = BinaryHeap::new();
+
+    for start in starts {
+        let (idx, inserted) = arena.insert(start, NO_PARENT);
+        if inserted {
+            costs.push(C::zero());
+            predecessors.push(Vec::new());
+            heap.push(Reverse((C::zero(), idx)));
+        }
+    }
+
+    let mut best: Option<C> = None;
+    let mut goals = Vec::new();
+
+    while let Some(Reverse((cost, idx))) = heap.pop() {
+        if cost > costs[idx] {
+            continue;
+        }
+        if let Some(best) = best {
+            if cost > best {
+                break;
+            }
+        }
+        if is_goal(&arena.states[idx]) {
+            best = Some(cost);
+            goals.push(idx);
+            continue;
+        }
+        for (next, step_cost) in successors(&arena.states[idx]) {
+            let new_cost = cost + step_cost;
+            let (next_idx, inserted) = arena.insert(next, idx);
+            if inserted {
+                costs.push(new_cost);
+                predecessors.push(vec![idx]);
+            } else if new_cost < costs[next_idx] {
+                costs[next_idx] = new_cost;
+                predecessors[next_idx] = vec![idx];
+            } else {
+                if new_cost == costs[next_idx] && !predecessors[next_idx].contains(&idx) {
+                    predecessors[next_idx].push(idx);
+                }
+                continue;
+            }
+            heap.push(Reverse((new_cost, next_idx)));
+        }
+    }
+
+    let best = best?;
+
+    // walk the predecessor graph backwards from every goal
+    let mut paths = Vec::new();
+    let mut stack: Vec<(usize, Vec<usize>)> = goals.into_iter().map(|g| (g, vec![g])).collect();
+    while let Some((idx, path)) = stack.pop() {
+        if predecessors[idx].is_empty() {
+            paths.push(path.iter().rev().map(|&i| arena.states[i].clone()).collect());
+            continue;
+        }
+        for &prev in predecessors[idx].iter() {
+            let mut new_path = path.clone();
+            new_path.push(prev);
+            stack.push((prev, new_path));
+        }
+    }
+
+    Some((paths, best))
+}
+
+// Number of distinct paths from the start states to the goal states. A path stops
+// at the first goal it reaches. The successor graph must not contain cycles
+pub fn count_paths<S, IS, FN, IN, FG>(starts: IS, mut successors: FN, mut is_goal: FG) -> u64
+where
+    S: Clone + Eq + Hash,
+    IS: IntoIterator<Item = S>,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = S>,
+    FG: FnMut(&S) -> bool,
+{
+    fn count<S, FN, IN, FG>(state: &S, successors: &mut FN, is_goal: &mut FG, cache: &mut HashMap<S, u64>) -> u64
+    where
+        S: Clone + Eq + Hash,
+        FN: FnMut(&S) -> IN,
+        IN: IntoIterator<Item = S>,
+        FG: FnMut(&S) -> bool,
+    {
+        if let Some(&result) = cache.get(state) {
+            return result;
+        }
+        let result = if is_goal(state) {
+            1
+        } else {
+            let mut result = 0;
+            for next in successors(state) {
+                result += count(&next, successors, is_goal, cache);
+            }
+            result
+        };
+        cache.insert(state.clone(), result);
+        result
+    }
+
+    let mut cache = HashMap::new();
+    let mut result = 0;
+    for start in starts {
+        result += count(&start, &mut successors, &mut is_goal, &mut cache);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{all_shortest_paths, astar, bfs, count_paths, dijkstra, reachable};
+    use crate::Point;
+
+    // cspell: disable
+    static MAZE: [&str; 5] = [
+        "S...#", //
+        ".##.#", //
+        ".#..#", //
+        ".#.##", //
+        "...E.", //
+    ];
+    // cspell: enable
+
+    fn maze_successors(point: &Point<i32>) -> Vec<Point<i32>> {
+        point
+            .neighbors_orthogonal()
+            .filter(|p| p.row >= 0 && p.col >= 0 && p.row < 5 && p.col < 5)
+            .filter(|p| MAZE[p.row as usize].as_bytes()[p.col as usize] != b'#')
+            .collect()
+    }
+
+    #[test]
+    fn bfs_test() {
+        let start = Point { row: 0, col: 0 };
+        let finish = Point { row: 4, col: 3 };
+        let (path, steps) = bfs([start], maze_successors, |p| *p == finish).unwrap();
+        assert_eq!(steps, 7);
+        assert_eq!(path.len(), 8);
+        assert_eq!(path[0], start);
+        assert_eq!(path[7], finish);
+
+        let wall = Point { row: 0, col: 4 };
+        assert!(bfs([start], maze_successors, |p| *p == wall).is_none());
+    }
+
+    #[test]
+    fn bfs_multiple_starts_test() {
+        let starts = [Point { row: 0, col: 0 }, Point { row: 2, col: 2 }];
+        let finish = Point { row: 4, col: 3 };
+        let (path, steps) = bfs(starts, maze_successors, |p| *p == finish).unwrap();
+        assert_eq!(steps, 3);
+        assert_eq!(path[0], Point { row: 2, col: 2 });
+    }
+
+    #[test]
+    fn reachable_test() {
+        let visited = reachable([Point { row: 0, col: 0 }], maze_successors);
+        assert_eq!(visited.len(), 16);
+        assert!(!visited.contains(&Point { row: 0, col: 4 }));
+    }
+
+    #[test]
+    fn dijkstra_test() {
+        // 0 -> 1 is expensive directly but cheap via 2
+        let edges = [(0, 1, 10), (0, 2, 1), (2, 1, 2), (1, 3, 1), (2, 3, 7)];
+        let successors = |n: &i32| {
+            edges
+                .iter()
+                .filter(move |(from, _, _)| from == n)
+                .map(|(_, to, cost)| (*to, *cost))
+                .collect::<Vec<_>>()
+        };
+        let (path, cost) = dijkstra([0], successors, |n| *n == 3).unwrap();
+        assert_eq!(cost, 4);
+        assert_eq!(path, vec![0, 2, 1, 3]);
+
+        assert!(dijkstra([3], successors, |n| *n == 0).is_none());
+    }
+
+    #[test]
+    fn astar_test() {
+        let start = Point { row: 0, col: 0 };
+        let finish = Point { row: 4, col: 3 };
+        let (path, cost) = astar(
+            [start],
+            |p| maze_successors(p).into_iter().map(|n| (n, 1)),
+            |p| (p.row - finish.row).abs() + (p.col - finish.col).abs(),
+            |p| *p == finish,
+        )
+        .unwrap();
+        assert_eq!(cost, 7);
+        assert_eq!(path.len(), 8);
+    }
+
+    #[test]
+    fn all_shortest_paths_test() {
+        // two equally cheap routes from 0 to 3 and one expensive one
+        let edges = [(0, 1, 1), (0, 2, 1), (1, 3, 1), (2, 3, 1), (0, 3, 5)];
+        let successors = |n: &i32| {
+            edges
+                .iter()
+                .filter(move |(from, _, _)| from == n)
+                .map(|(_, to, cost)| (*to, *cost))
+                .collect::<Vec<_>>()
+        };
+        let (mut paths, cost) = all_shortest_paths([0], successors, |n| *n == 3).unwrap();
+        paths.sort();
+        assert_eq!(cost, 2);
+        assert_eq!(paths, vec![vec![0, 1, 3], vec![0, 2, 3]]);
+    }
+
+    #[test]
+    fn count_paths_test() {
+        // only moving right or down in a 3x3 grid
+        let successors = |p: &Point<i32>| {
+            [
+                Point {
+                    row: p.row + 1,
+                    col: p.col,
+                },
+                Point {
+                    row: p.row,
+                    col: p.col + 1,
+                },
+            ]
+            .into_iter()
+            .filter(|p| p.row < 3 && p.col < 3)
+        };
+        let finish = Point { row: 2, col: 2 };
+        assert_eq!(count_paths([Point { row: 0, col: 0 }], successors, |p| *p == finish), 6);
+        assert_eq!(
+            count_paths([Point { row: 0, col: 0 }, Point { row: 1, col: 1 }], successors, |p| *p
+                == finish),
+            8
+        );
+    }
+}
@@ -0,0 +1,88 @@
+// Choosing k elements of a sequence, keeping their order, so that the chosen
+// subsequence is lexicographically the largest (or the smallest) one. Classic
+// monotonic stack: an element is dropped as soon as a better one follows it
+// and there are still elements left to drop, so it's O(n).
+// Equal elements are never dropped in favour of each other, ties go to the
+// earliest indices.
+
+fn select<T, F>(items: &[T], k: usize, mut is_better: F) -> Vec<usize>
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    assert!(k <= items.len(), "can't choose {} of {} elements", k, items.len());
+    let mut drops = items.len() - k;
+    let mut stack: Vec<usize> = Vec::with_capacity(items.len());
+    for (i, item) in items.iter().enumerate() {
+        while drops > 0 && stack.last().is_some_and(|&top| is_better(item, &items[top])) {
+            stack.pop();
+            drops -= 1;
+        }
+        stack.push(i);
+    }
+    stack.truncate(k);
+    stack
+}
+
+// Indices of the lexicographically largest subsequence of length k
+pub fn max_subsequence<T: Ord>(items: &[T], k: usize) -> Vec<usize> {
+    select(items, k, |a, b| a > b)
+}
+
+// Indices of the lexicographically smallest subsequence of length k
+pub fn min_subsequence<T: Ord>(items: &[T], k: usize) -> Vec<usize> {
+    select(items, k, |a, b| a < b)
+}
+
+fn to_number<D: Copy + Into<u64>>(digits: &[D], indices: &[usize]) -> u64 {
+    indices.iter().fold(0, |acc, &i| acc * 10 + digits[i].into())
+}
+
+// The largest k-digit number made of the digits in their order,
+// along with the indices of the digits used
+pub fn max_number<D: Copy + Ord + Into<u64>>(digits: &[D], k: usize) -> (Vec<usize>, u64) {
+    let indices = max_subsequence(digits, k);
+    let value = to_number(digits, &indices);
+    (indices, value)
+}
+
+// The smallest k-digit number made of the digits in their order (leading zeros
+// are allowed), along with the indices of the digits used
+pub fn min_number<D: Copy + Ord + Into<u64>>(digits: &[D], k: usize) -> (Vec<usize>, u64) {
+    let indices = min_subsequence(digits, k);
+    let value = to_number(digits, &indices);
+    (indices, value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{max_number, max_subsequence, min_number, min_subsequence};
+
+    #[test]
+    fn subsequence_test() {
+        let items = ['b', 'c', 'a', 'c', 'b'];
+        assert_eq!(max_subsequence(&items, 2), vec![1, 3]);
+        assert_eq!(max_subsequence(&items, 3), vec![1, 3, 4]);
+        assert_eq!(min_subsequence(&items, 2), vec![2, 4]);
+        assert_eq!(max_subsequence(&items, 0), Vec::<usize>::new());
+        assert_eq!(max_subsequence(&items, 5), vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn ties_test() {
+        assert_eq!(max_subsequence(&[8, 8, 8, 7], 2), vec![0, 1]);
+        assert_eq!(min_subsequence(&[1, 1, 2, 1], 2), vec![0, 1]);
+    }
+
+    #[test]
+    fn number_test() {
+        let digits: Vec<u8> = vec![1, 4, 3, 2, 2, 1, 9];
+        assert_eq!(max_number(&digits, 3), (vec![1, 2, 6], 439));
+        assert_eq!(min_number(&digits, 3), (vec![0, 5, 6], 119));
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_many_test() {
+        max_subsequence(&[1, 2], 3);
+    }
+}
@@ -0,0 +1,72 @@
+// Checks shared by the year crates
+
+use std::path::Path;
+
+// Anything that reads files. Puzzle solvers get their input as text from the
+// runner, this only catches the obvious ways around that
+const RAW_FILE_IO: [&str; 6] = [
+    "std::fs",
+    "fs::read",
+    "File::open",
+    "BufReader",
+    "OpenOptions",
+    "input::read",
+];
+
+// Returns "file:line: code" for every line of .rs files in the directory
+// (not recursive) that does raw file I/O. Comments are ignored
+pub fn find_raw_file_io(dir: &Path) -> Vec<String> {
+    let mut paths: Vec<_> = std::fs::read_dir(dir)
+        .unwrap_or_else(|err| panic!("Failed to read {}: {}", dir.display(), err))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .collect();
+    paths.sort();
+
+    let mut result = Vec::new();
+    for path in paths.iter() {
+        let text = std::fs::read_to_string(path).unwrap();
+        result.extend(find_raw_file_io_in_text(&text).map(|(idx, line)| {
+            let file_name = path.file_name().unwrap().to_string_lossy();
+            format!("{}:{}: {}", file_name, idx + 1, line.trim())
+        }));
+    }
+    result
+}
+
+fn find_raw_file_io_in_text(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.lines().enumerate().filter(|(_, line)| {
+        let code = line.split("//").next().unwrap_or_default();
+        RAW_FILE_IO.iter().any(|pattern| code.contains(pattern))
+    })
+}
+
+// Meant to be called from a test in every year crate:
+// aoc::testing::assert_no_raw_file_io(env!("CARGO_MANIFEST_DIR"));
+pub fn assert_no_raw_file_io(dir: &str) {
+    let found = find_raw_file_io(Path::new(dir));
+    assert!(
+        found.is_empty(),
+        "Puzzle modules must take their input as text, file I/O found at:\n{}",
+        found.join("\n")
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::find_raw_file_io_in_text;
+
+    #[test]
+    fn find_raw_file_io_test() {
+        let text = "use std::fs::File;
+fn solve(file_name: &str) -> String {
+    // std::fs::read_to_string(file_name) is not allowed
+    let text = aoc::input::read(file_name);
+    let file = File::open(file_name).unwrap();
+    for line in std::fs::read_to_string(file_name).unwrap().lines() {}
+    for line in input.lines() {}
+}";
+        let found: Vec<usize> = find_raw_file_io_in_text(text).map(|(idx, _)| idx).collect();
+        assert_eq!(found, vec![0, 3, 4, 5]);
+    }
+}
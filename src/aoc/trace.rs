@@ -0,0 +1,211 @@
+// Debug output for solutions that doesn't get mixed with the answers.
+// aoc::trace!("expanding {:?}", node) takes format! arguments and does nothing
+// unless the runner was started with --trace. Trace lines go to stderr, or to
+// a log file per puzzle when --trace-dir is given, and are prefixed with the
+// puzzle being solved.
+//
+// Release builds compile tracing out, enable the "trace" feature of the aoc
+// crate to keep it.
+
+use std::fmt;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+pub const COMPILED: bool = cfg!(any(debug_assertions, feature = "trace"));
+
+#[derive(Debug, Default, Clone)]
+pub struct Settings {
+    pub enabled: bool,
+    // write every puzzle trace to its own file in this directory instead of stderr
+    pub log_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Context {
+    pub year: i32,
+    pub day: i32,
+    pub part: i32,
+    pub input: String,
+}
+
+impl fmt::Display for Context {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let input = std::path::Path::new(&self.input)
+            .file_name()
+            .map_or(self.input.clone(), |name| name.to_string_lossy().to_string());
+        write!(f, "{} day {:0>2} part {} ({})", self.year, self.day, self.part, input)
+    }
+}
+
+#[derive(Default)]
+struct State {
+    log_dir: Option<PathBuf>,
+    context: Option<Context>,
+    log: Option<BufWriter<File>>,
+}
+
+// checked on every trace! call, so it's kept out of the mutex
+#[cfg(not(test))]
+static ENABLED: AtomicBool = AtomicBool::new(false);
+#[cfg(not(test))]
+static STATE: Mutex<Option<State>> = Mutex::new(None);
+
+// Tests run in parallel threads, so every test gets its own settings and
+// can't see traces of the others
+#[cfg(test)]
+thread_local! {
+    static ENABLED: AtomicBool = const { AtomicBool::new(false) };
+    static STATE: Mutex<Option<State>> = const { Mutex::new(None) };
+}
+
+fn with_enabled<R>(f: impl FnOnce(&AtomicBool) -> R) -> R {
+    #[cfg(not(test))]
+    return f(&ENABLED);
+    #[cfg(test)]
+    return ENABLED.with(f);
+}
+
+fn with_state<R>(f: impl FnOnce(&mut Option<State>) -> R) -> R {
+    #[cfg(not(test))]
+    return f(&mut STATE.lock().unwrap());
+    #[cfg(test)]
+    return STATE.with(|state| f(&mut state.lock().unwrap()));
+}
+
+// Called by the runner
+pub fn configure(settings: Settings) {
+    if let Some(dir) = settings.log_dir.as_ref() {
+        std::fs::create_dir_all(dir).unwrap_or_else(|err| panic!("Failed to create {}: {}", dir.display(), err));
+    }
+    with_state(|state| {
+        *state = Some(State {
+            log_dir: settings.log_dir,
+            ..Default::default()
+        })
+    });
+    with_enabled(|enabled| enabled.store(COMPILED && settings.enabled, Ordering::Relaxed));
+}
+
+pub fn is_enabled() -> bool {
+    COMPILED && with_enabled(|enabled| enabled.load(Ordering::Relaxed))
+}
+
+// Called by aoc::solve around every puzzle run. With a log directory, every
+// puzzle gets a file named after it, all inputs of the puzzle go there
+pub fn set_context(context: Option<Context>) {
+    with_state(|state| {
+        if let Some(state) = state.as_mut() {
+            set_state_context(state, context);
+        }
+    })
+}
+
+fn set_state_context(state: &mut State, context: Option<Context>) {
+    if let Some(log) = state.log.as_mut() {
+        log.flush().expect("Failed to write a trace");
+    }
+    state.log = None;
+
+    if let (Some(dir), Some(context)) = (state.log_dir.as_ref(), context.as_ref()) {
+        let path = dir.join(format!(
+            "{}_day_{:0>2}_part_{}.log",
+            context.year, context.day, context.part
+        ));
+        let file = File::options()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .unwrap_or_else(|err| panic!("Failed to open {}: {}", path.display(), err));
+        let mut log = BufWriter::new(file);
+        writeln!(log, "=== {}", context).expect("Failed to write a trace");
+        state.log = Some(log);
+    }
+    state.context = context;
+}
+
+// Use trace! instead
+pub fn write(args: fmt::Arguments) {
+    with_state(|state| {
+        let Some(state) = state.as_mut() else {
+            return;
+        };
+        match (state.log.as_mut(), state.context.as_ref()) {
+            (Some(log), _) => writeln!(log, "{}", args).expect("Failed to write a trace"),
+            (None, Some(context)) => eprintln!("[{}] {}", context, args),
+            (None, None) => eprintln!("{}", args),
+        }
+    })
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::trace::is_enabled() {
+            $crate::trace::write(format_args!($($arg)*));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{configure, is_enabled, set_context, Context, Settings, COMPILED};
+
+    #[test]
+    fn context_test() {
+        let context = Context {
+            year: 2023,
+            day: 7,
+            part: 2,
+            input: "/inputs/2023/day_07.txt".to_owned(),
+        };
+        assert_eq!(context.to_string(), "2023 day 07 part 2 (day_07.txt)");
+    }
+
+    // the settings are per thread in tests, other tests tracing in parallel
+    // don't end up in the log
+    #[test]
+    fn configure_test() {
+        configure(Settings::default());
+        assert!(!is_enabled());
+        configure(Settings {
+            enabled: true,
+            log_dir: None,
+        });
+        assert_eq!(is_enabled(), COMPILED);
+
+        let dir = std::env::temp_dir().join(format!("aoc_trace_test_{}", std::process::id()));
+        configure(Settings {
+            enabled: true,
+            log_dir: Some(dir.clone()),
+        });
+        assert!(dir.is_dir());
+        let context = Context {
+            year: 2024,
+            day: 6,
+            part: 1,
+            input: "/inputs/2024/day_06.txt".to_owned(),
+        };
+        set_context(Some(context.clone()));
+        crate::trace!("step {}", 1);
+        set_context(Some(Context {
+            input: "/inputs/2024/example.txt".to_owned(),
+            ..context
+        }));
+        crate::trace!("step {}", 2);
+        set_context(None);
+        crate::trace!("not logged");
+
+        let log = std::fs::read_to_string(dir.join("2024_day_06_part_1.log")).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        configure(Settings::default());
+        let expected = if COMPILED {
+            "=== 2024 day 06 part 1 (day_06.txt)\nstep 1\n=== 2024 day 06 part 1 (example.txt)\nstep 2\n"
+        } else {
+            "=== 2024 day 06 part 1 (day_06.txt)\n=== 2024 day 06 part 1 (example.txt)\n"
+        };
+        assert_eq!(log, expected);
+    }
+}
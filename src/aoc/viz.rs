@@ -0,0 +1,304 @@
+// Terminal rendering of grids for debugging. Build a Frame from a Field or
+// a set of points and pass it to show(). Nothing is printed unless the runner
+// was started with --visualize or --dump-frames, so it's fine to leave show()
+// calls in solutions. Frame building isn't free though, wrap it into
+// is_enabled() check in hot loops:
+//
+// if aoc::viz::is_enabled() {
+//     aoc::viz::show(&Frame::from_field(&field, |node| Cell::new(*node)));
+// }
+
+use std::fmt;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::{Field, Point};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    // SGR parameters, the base is 30 for foreground and 40 for background
+    fn sgr(&self, base: u8) -> String {
+        let offset = match *self {
+            Color::Black => 0,
+            Color::Red => 1,
+            Color::Green => 2,
+            Color::Yellow => 3,
+            Color::Blue => 4,
+            Color::Magenta => 5,
+            Color::Cyan => 6,
+            Color::White => 7,
+            Color::Rgb(r, g, b) => return format!("{};2;{};{};{}", base + 8, r, g, b),
+        };
+        (base + offset).to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub ch: char,
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+}
+
+impl Cell {
+    pub fn new(ch: char) -> Self {
+        Cell { ch, fg: None, bg: None }
+    }
+
+    pub fn fg(self, color: Color) -> Self {
+        Cell {
+            fg: Some(color),
+            ..self
+        }
+    }
+
+    pub fn bg(self, color: Color) -> Self {
+        Cell {
+            bg: Some(color),
+            ..self
+        }
+    }
+
+    fn has_same_style(&self, other: &Cell) -> bool {
+        self.fg == other.fg && self.bg == other.bg
+    }
+
+    fn is_plain(&self) -> bool {
+        self.fg.is_none() && self.bg.is_none()
+    }
+}
+
+impl From<char> for Cell {
+    fn from(ch: char) -> Self {
+        Cell::new(ch)
+    }
+}
+
+const RESET: &str = "\x1b[0m";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    cells: Vec<Cell>,
+    row_count: usize,
+    col_count: usize,
+}
+
+impl Frame {
+    pub fn with_size(row_count: usize, col_count: usize, background: Cell) -> Self {
+        Frame {
+            cells: vec![background; row_count * col_count],
+            row_count,
+            col_count,
+        }
+    }
+
+    // Every node is drawn with the cell returned by the style closure
+    pub fn from_field<Node, F>(field: &Field<Node>, mut style: F) -> Self
+    where
+        Node: Default + Clone,
+        F: FnMut(&Node) -> Cell,
+    {
+        let (row_count, col_count) = (field.get_row_count(), field.get_col_count());
+        let mut cells = Vec::with_capacity(row_count * col_count);
+        for row in 0..row_count {
+            for col in 0..col_count {
+                cells.push(style(field.get(row, col)));
+            }
+        }
+        Frame {
+            cells,
+            row_count,
+            col_count,
+        }
+    }
+
+    // Points from the set are drawn with the `on` cell, the rest with `off` one.
+    // Points outside of the frame are skipped
+    pub fn from_points<I>(row_count: usize, col_count: usize, points: I, on: Cell, off: Cell) -> Self
+    where
+        I: IntoIterator<Item = Point<i32>>,
+    {
+        let mut result = Frame::with_size(row_count, col_count, off);
+        for point in points {
+            result.set(&point, on);
+        }
+        result
+    }
+
+    pub fn get_row_count(&self) -> usize {
+        self.row_count
+    }
+
+    pub fn get_col_count(&self) -> usize {
+        self.col_count
+    }
+
+    // Draws over a single cell, e.g. to highlight the current position.
+    // Does nothing for points outside of the frame
+    pub fn set(&mut self, point: &Point<i32>, cell: Cell) {
+        if point.row < 0 || point.col < 0 {
+            return;
+        }
+        let (row, col) = (point.row as usize, point.col as usize);
+        if row < self.row_count && col < self.col_count {
+            self.cells[row * self.col_count + col] = cell;
+        }
+    }
+
+    fn rows(&self) -> impl Iterator<Item = &[Cell]> {
+        self.cells.chunks(self.col_count.max(1))
+    }
+
+    // Text with ANSI escape codes, escape sequences are only emitted when the style changes
+    pub fn to_ansi(&self) -> String {
+        let mut result = String::new();
+        for row in self.rows() {
+            let mut current = Cell::new(' ');
+            for cell in row {
+                if !cell.has_same_style(&current) {
+                    result.push_str(RESET);
+                    let mut params = Vec::new();
+                    params.extend(cell.fg.map(|c| c.sgr(30)));
+                    params.extend(cell.bg.map(|c| c.sgr(40)));
+                    if !params.is_empty() {
+                        result.push_str(&format!("\x1b[{}m", params.join(";")));
+                    }
+                    current = *cell;
+                }
+                result.push(cell.ch);
+            }
+            if !current.is_plain() {
+                result.push_str(RESET);
+            }
+            result.push('\n');
+        }
+        result
+    }
+}
+
+// Plain text without any styling
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell.ch)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Settings {
+    // draw frames in the terminal
+    pub visualize: bool,
+    // pause after every frame drawn in the terminal
+    pub frame_delay: Duration,
+    // append every frame as plain text to this file
+    pub dump_file: Option<PathBuf>,
+}
+
+struct State {
+    visualize: bool,
+    frame_delay: Duration,
+    dump: Option<std::fs::File>,
+    frame_count: usize,
+}
+
+static STATE: Mutex<Option<State>> = Mutex::new(None);
+
+// Called by the runner, the dump file is truncated
+pub fn configure(settings: Settings) {
+    let dump = settings.dump_file.map(|path| {
+        std::fs::File::create(&path).unwrap_or_else(|err| panic!("Failed to create {}: {}", path.display(), err))
+    });
+    let state = State {
+        visualize: settings.visualize,
+        frame_delay: settings.frame_delay,
+        dump,
+        frame_count: 0,
+    };
+    *STATE.lock().unwrap() = Some(state);
+}
+
+pub fn is_enabled() -> bool {
+    STATE
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|state| state.visualize || state.dump.is_some())
+}
+
+// Draws the frame over the previous one and/or appends it to the dump file
+pub fn show(frame: &Frame) {
+    let mut guard = STATE.lock().unwrap();
+    let Some(state) = guard.as_mut() else {
+        return;
+    };
+    state.frame_count += 1;
+    if let Some(dump) = state.dump.as_mut() {
+        write!(dump, "frame {}\n{}\n", state.frame_count, frame).expect("Failed to write a frame");
+    }
+    if state.visualize {
+        // move the cursor home and clear the screen
+        print!("\x1b[H\x1b[2J{}", frame.to_ansi());
+        std::io::stdout().flush().unwrap();
+        std::thread::sleep(state.frame_delay);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cell, Color, Frame};
+    use crate::{Field, Point};
+
+    #[test]
+    fn from_field_test() {
+        let field = Field::from_flat_vector(vec![1, 0, 0, 1, 1, 0], 3).unwrap();
+        let frame = Frame::from_field(&field, |&n| if n == 1 { '#'.into() } else { '.'.into() });
+        assert_eq!(frame.to_string(), "#..\n##.\n");
+    }
+
+    #[test]
+    fn from_points_test() {
+        let points = vec![
+            Point { row: 0, col: 1 },
+            Point { row: 1, col: 2 },
+            Point { row: 5, col: 5 },
+        ];
+        let mut frame = Frame::from_points(2, 3, points, Cell::new('#'), Cell::new('.'));
+        frame.set(&Point { row: 1, col: 0 }, Cell::new('@'));
+        frame.set(&Point { row: -1, col: 0 }, Cell::new('@'));
+        assert_eq!(frame.to_string(), ".#.\n@.#\n");
+    }
+
+    #[test]
+    fn to_ansi_test() {
+        let points = vec![Point { row: 0, col: 1 }, Point { row: 0, col: 2 }];
+        let on = Cell::new('#').fg(Color::Red);
+        let mut frame = Frame::from_points(2, 3, points, on, Cell::new('.'));
+        frame.set(
+            &Point { row: 1, col: 1 },
+            Cell::new('@').fg(Color::Rgb(1, 2, 3)).bg(Color::Blue),
+        );
+        assert_eq!(
+            frame.to_ansi(),
+            ".\x1b[0m\x1b[31m##\x1b[0m\n.\x1b[0m\x1b[38;2;1;2;3;44m@\x1b[0m.\n"
+        );
+    }
+}
@@ -0,0 +1,527 @@
+// A small register machine for the assembly-like puzzles. A puzzle defines its
+// instruction type implementing Instruction and a table of textual patterns to
+// parse it from:
+//
+// let set = InstructionSet::new(&["a", "b"])
+//     .add("inc {}", |args| Some(Op::Inc(args[0])))
+//     .add("jnz {}, {}", |args| Some(Op::Jnz(args[0], args[1])));
+// let mut machine = Machine::new(set.parse_program(text)?, set.registers());
+// machine.run();
+//
+// Every {} placeholder matches an operand: a register name from the set or an
+// integer with an optional sign. A digit count like {3} restricts it to an
+// unsigned value of 1 to that many digits, which is what the puzzles hiding
+// instructions in corrupted memory need. Executed instructions are printed by
+// aoc::trace! along with the registers, so running with --trace shows the
+// whole execution.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::parse::{self, ParseError};
+
+pub type Word = i64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operand {
+    Register(usize),
+    Value(Word),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Registers {
+    names: Vec<String>,
+    values: Vec<Word>,
+}
+
+impl Registers {
+    // All registers start at zero
+    pub fn new(names: &[&str]) -> Self {
+        Registers {
+            names: names.iter().map(|name| name.to_string()).collect(),
+            values: vec![0; names.len()],
+        }
+    }
+
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+
+    pub fn get(&self, name: &str) -> Word {
+        self.values[self.index_or_panic(name)]
+    }
+
+    pub fn set(&mut self, name: &str, value: Word) {
+        let index = self.index_or_panic(name);
+        self.values[index] = value;
+    }
+
+    // Register content or the immediate value
+    pub fn value(&self, operand: Operand) -> Word {
+        match operand {
+            Operand::Register(index) => self.values[index],
+            Operand::Value(value) => value,
+        }
+    }
+
+    fn index_or_panic(&self, name: &str) -> usize {
+        self.index_of(name)
+            .unwrap_or_else(|| panic!("unknown register `{}`", name))
+    }
+}
+
+impl Index<usize> for Registers {
+    type Output = Word;
+
+    fn index(&self, index: usize) -> &Word {
+        &self.values[index]
+    }
+}
+
+impl IndexMut<usize> for Registers {
+    fn index_mut(&mut self, index: usize) -> &mut Word {
+        &mut self.values[index]
+    }
+}
+
+// "a=1 b=-2"
+impl fmt::Display for Registers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (name, value)) in self.names.iter().zip(self.values.iter()).enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}={}", name, value)?;
+        }
+        Ok(())
+    }
+}
+
+// What to do after an instruction is executed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    Next,
+    // relative to the current instruction
+    Jump(Word),
+    Halt,
+}
+
+pub trait Instruction {
+    fn execute(&self, registers: &mut Registers) -> Flow;
+}
+
+// Why the machine stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    // a Halt instruction or a jump out of the program
+    Halted,
+    // about to execute the instruction at the breakpoint
+    Breakpoint(usize),
+    // about to execute the instruction at the given address in the exact
+    // same state as before, so the machine would run forever
+    Loop(usize),
+}
+
+pub struct Machine<I> {
+    program: Vec<I>,
+    pub registers: Registers,
+    ip: Word,
+    steps: usize,
+    breakpoints: HashSet<usize>,
+    // the breakpoint the last run stopped at, the next run starts by
+    // executing the instruction at it
+    stopped_at: Option<usize>,
+}
+
+impl<I> Machine<I>
+where
+    I: Instruction + fmt::Debug,
+{
+    pub fn new(program: Vec<I>, registers: Registers) -> Self {
+        Machine {
+            program,
+            registers,
+            ip: 0,
+            steps: 0,
+            breakpoints: HashSet::new(),
+            stopped_at: None,
+        }
+    }
+
+    pub fn program(&self) -> &[I] {
+        &self.program
+    }
+
+    // Address of the next instruction
+    pub fn ip(&self) -> Word {
+        self.ip
+    }
+
+    // Number of instructions executed so far
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn add_breakpoint(&mut self, address: usize) {
+        self.breakpoints.insert(address);
+    }
+
+    pub fn remove_breakpoint(&mut self, address: usize) {
+        self.breakpoints.remove(&address);
+    }
+
+    fn current(&self) -> Option<usize> {
+        if self.ip < 0 || self.ip as usize >= self.program.len() {
+            return None;
+        }
+        Some(self.ip as usize)
+    }
+
+    // Executes one instruction, ignoring breakpoints
+    pub fn step(&mut self) -> Option<Stop> {
+        self.stopped_at = None;
+        let Some(address) = self.current() else {
+            return Some(Stop::Halted);
+        };
+        let instruction = &self.program[address];
+        let flow = instruction.execute(&mut self.registers);
+        crate::trace!("{:>4}: {:?} -> {}", address, instruction, self.registers);
+        self.steps += 1;
+        match flow {
+            Flow::Next => self.ip += 1,
+            Flow::Jump(offset) => self.ip += offset,
+            Flow::Halt => {
+                self.ip = self.program.len() as Word;
+                return Some(Stop::Halted);
+            }
+        }
+        None
+    }
+
+    // Runs until the program halts or reaches a breakpoint. Resuming from a
+    // breakpoint executes the instruction at it
+    pub fn run(&mut self) -> Stop {
+        self.run_with(|_| false)
+    }
+
+    // Same as run() but also stops when the machine gets into the same state
+    // twice. Remembers every state, so it's meant for short programs
+    pub fn run_detecting_loop(&mut self) -> Stop {
+        let mut seen: HashSet<(Word, Vec<Word>)> = HashSet::new();
+        self.run_with(move |machine| !seen.insert((machine.ip, machine.registers.values.clone())))
+    }
+
+    fn run_with<F>(&mut self, mut is_loop: F) -> Stop
+    where
+        F: FnMut(&Machine<I>) -> bool,
+    {
+        loop {
+            if let Some(address) = self.current() {
+                if self.stopped_at != Some(address) && self.breakpoints.contains(&address) {
+                    crate::trace!("breakpoint at {}: {}", address, self.registers);
+                    self.stopped_at = Some(address);
+                    return Stop::Breakpoint(address);
+                }
+                if is_loop(self) {
+                    crate::trace!("loop at {}: {}", address, self.registers);
+                    return Stop::Loop(address);
+                }
+            }
+            if let Some(stop) = self.step() {
+                return stop;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Literal(String),
+    Operand,
+    // unsigned value of at most this many digits
+    Digits(usize),
+}
+
+// Builds the instruction from the operands matched by the pattern. None
+// rejects the match, e.g. when a value is out of range
+pub type Builder<I> = fn(&[Operand]) -> Option<I>;
+
+struct Entry<I> {
+    pattern: String,
+    tokens: Vec<Token>,
+    build: Builder<I>,
+}
+
+// Table of instruction patterns and the register names they may refer to
+pub struct InstructionSet<I> {
+    register_names: Vec<String>,
+    entries: Vec<Entry<I>>,
+}
+
+impl<I> InstructionSet<I> {
+    pub fn new(register_names: &[&str]) -> Self {
+        InstructionSet {
+            register_names: register_names.iter().map(|name| name.to_string()).collect(),
+            entries: Vec::new(),
+        }
+    }
+
+    // Panics on a malformed placeholder
+    pub fn add(mut self, pattern: &str, build: Builder<I>) -> Self {
+        let mut tokens = Vec::new();
+        let mut rest = pattern;
+        while let Some(open) = rest.find('{') {
+            let close = rest[open..]
+                .find('}')
+                .map(|i| open + i)
+                .unwrap_or_else(|| panic!("unclosed placeholder in `{}`", pattern));
+            if open > 0 {
+                tokens.push(Token::Literal(rest[..open].to_owned()));
+            }
+            let token = match &rest[open + 1..close] {
+                "" => Token::Operand,
+                digits => match digits.parse() {
+                    Ok(count) if count > 0 => Token::Digits(count),
+                    _ => panic!("bad placeholder `{{{}}}` in `{}`", digits, pattern),
+                },
+            };
+            tokens.push(token);
+            rest = &rest[close + 1..];
+        }
+        if !rest.is_empty() {
+            tokens.push(Token::Literal(rest.to_owned()));
+        }
+        self.entries.push(Entry {
+            pattern: pattern.to_owned(),
+            tokens,
+            build,
+        });
+        self
+    }
+
+    // Fresh registers for a machine running the parsed programs
+    pub fn registers(&self) -> Registers {
+        let names: Vec<&str> = self.register_names.iter().map(|n| n.as_str()).collect();
+        Registers::new(&names)
+    }
+
+    // Parses the instruction that occupies the whole line
+    pub fn parse_line(&self, line: &str) -> parse::Result<I> {
+        let line = line.trim();
+        self.entries
+            .iter()
+            .find_map(|entry| {
+                let (operands, len) = self.match_entry(entry, line)?;
+                if len != line.len() {
+                    return None;
+                }
+                (entry.build)(&operands)
+            })
+            .ok_or_else(|| ParseError::new(1, format!("unknown instruction `{}`", line)))
+    }
+
+    // One instruction per line, blank lines are skipped
+    pub fn parse_program(&self, text: &str) -> parse::Result<Vec<I>> {
+        text.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| self.parse_line(line).map_err(|e| e.at_line(i + 1)))
+            .collect()
+    }
+
+    // All instructions found anywhere in the text, everything in between is
+    // skipped. For puzzles where the program is hidden in corrupted memory
+    pub fn find_all(&self, text: &str) -> Vec<I> {
+        let mut result = Vec::new();
+        let mut pos = 0;
+        while pos < text.len() {
+            let rest = &text[pos..];
+            let found = self.entries.iter().find_map(|entry| {
+                let (operands, len) = self.match_entry(entry, rest)?;
+                Some(((entry.build)(&operands)?, len))
+            });
+            match found {
+                Some((instruction, len)) => {
+                    result.push(instruction);
+                    pos += len;
+                }
+                None => pos += rest.chars().next().map_or(1, |ch| ch.len_utf8()),
+            }
+        }
+        result
+    }
+
+    // Operands and the length of the matched prefix of the text
+    fn match_entry(&self, entry: &Entry<I>, text: &str) -> Option<(Vec<Operand>, usize)> {
+        let mut operands = Vec::new();
+        let mut rest = text;
+        for token in entry.tokens.iter() {
+            match token {
+                Token::Literal(literal) => rest = rest.strip_prefix(literal.as_str())?,
+                Token::Operand => {
+                    let (operand, len) = self.match_operand(rest)?;
+                    operands.push(operand);
+                    rest = &rest[len..];
+                }
+                Token::Digits(max_len) => {
+                    let len = rest.bytes().take_while(|b| b.is_ascii_digit()).count();
+                    if len == 0 || len > *max_len {
+                        return None;
+                    }
+                    operands.push(Operand::Value(rest[..len].parse().ok()?));
+                    rest = &rest[len..];
+                }
+            }
+        }
+        Some((operands, text.len() - rest.len()))
+    }
+
+    fn match_operand(&self, text: &str) -> Option<(Operand, usize)> {
+        let bytes = text.as_bytes();
+        let sign = usize::from(bytes.first().is_some_and(|&b| b == b'-' || b == b'+'));
+        let digits = bytes[sign..].iter().take_while(|b| b.is_ascii_digit()).count();
+        if digits > 0 {
+            let value = text[..sign + digits].parse().ok()?;
+            return Some((Operand::Value(value), sign + digits));
+        }
+        // the longest register name wins, so "ab" isn't taken for "a"
+        self.register_names
+            .iter()
+            .enumerate()
+            .filter(|(_, name)| text.starts_with(name.as_str()))
+            .max_by_key(|(_, name)| name.len())
+            .map(|(index, name)| (Operand::Register(index), name.len()))
+    }
+}
+
+impl<I> fmt::Debug for InstructionSet<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let patterns: Vec<&str> = self.entries.iter().map(|e| e.pattern.as_str()).collect();
+        f.debug_struct("InstructionSet")
+            .field("register_names", &self.register_names)
+            .field("patterns", &patterns)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Flow, Instruction, InstructionSet, Machine, Operand, Registers, Stop};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Op {
+        Cpy(Operand, Operand),
+        Inc(Operand),
+        Dec(Operand),
+        Jnz(Operand, Operand),
+    }
+
+    fn register(operand: Operand) -> usize {
+        match operand {
+            Operand::Register(index) => index,
+            Operand::Value(_) => panic!("register expected"),
+        }
+    }
+
+    impl Instruction for Op {
+        fn execute(&self, registers: &mut Registers) -> Flow {
+            match *self {
+                Op::Cpy(from, to) => registers[register(to)] = registers.value(from),
+                Op::Inc(r) => registers[register(r)] += 1,
+                Op::Dec(r) => registers[register(r)] -= 1,
+                Op::Jnz(cond, offset) => {
+                    if registers.value(cond) != 0 {
+                        return Flow::Jump(registers.value(offset));
+                    }
+                }
+            }
+            Flow::Next
+        }
+    }
+
+    fn instruction_set() -> InstructionSet<Op> {
+        InstructionSet::new(&["a", "b"])
+            .add("cpy {} {}", |args| Some(Op::Cpy(args[0], args[1])))
+            .add("inc {}", |args| Some(Op::Inc(args[0])))
+            .add("dec {}", |args| Some(Op::Dec(args[0])))
+            .add("jnz {} {}", |args| Some(Op::Jnz(args[0], args[1])))
+    }
+
+    #[test]
+    fn parse_program_test() {
+        let set = instruction_set();
+        let program = set.parse_program("cpy 41 a\ninc a\n\ndec b\njnz a -2\n").unwrap();
+        assert_eq!(
+            program,
+            vec![
+                Op::Cpy(Operand::Value(41), Operand::Register(0)),
+                Op::Inc(Operand::Register(0)),
+                Op::Dec(Operand::Register(1)),
+                Op::Jnz(Operand::Register(0), Operand::Value(-2)),
+            ]
+        );
+
+        let err = set.parse_program("inc a\nmul a b").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(set.parse_line("inc c").is_err());
+        assert!(set.parse_line("inc a b").is_err());
+    }
+
+    #[test]
+    fn run_test() {
+        let set = instruction_set();
+        let program = set.parse_program("cpy 3 b\ninc a\ninc a\ndec b\njnz b -3").unwrap();
+        let mut machine = Machine::new(program, set.registers());
+        assert_eq!(machine.run(), Stop::Halted);
+        assert_eq!(machine.registers.get("a"), 6);
+        assert_eq!(machine.registers.to_string(), "a=6 b=0");
+        assert_eq!(machine.steps(), 13);
+    }
+
+    #[test]
+    fn breakpoint_test() {
+        let set = instruction_set();
+        let program = set.parse_program("cpy 2 b\ninc a\ndec b\njnz b -2").unwrap();
+        let mut machine = Machine::new(program, set.registers());
+        machine.add_breakpoint(2);
+        assert_eq!(machine.run(), Stop::Breakpoint(2));
+        assert_eq!(machine.registers.get("a"), 1);
+        assert_eq!(machine.run(), Stop::Breakpoint(2));
+        assert_eq!(machine.registers.get("a"), 2);
+        machine.remove_breakpoint(2);
+        assert_eq!(machine.run(), Stop::Halted);
+
+        // a fresh machine stops before the first instruction too
+        let program = set.parse_program("inc a\njnz a 2\ninc a").unwrap();
+        let mut machine = Machine::new(program, set.registers());
+        machine.add_breakpoint(0);
+        assert_eq!(machine.run(), Stop::Breakpoint(0));
+        assert_eq!(machine.steps(), 0);
+        assert_eq!(machine.run(), Stop::Halted);
+        assert_eq!(machine.registers.get("a"), 1);
+    }
+
+    #[test]
+    fn loop_test() {
+        let set = instruction_set();
+        let program = set.parse_program("inc a\ndec a\njnz 1 -2").unwrap();
+        let mut machine = Machine::new(program, set.registers());
+        assert_eq!(machine.run_detecting_loop(), Stop::Loop(0));
+        assert_eq!(machine.steps(), 3);
+    }
+
+    #[test]
+    fn find_all_test() {
+        let set = InstructionSet::new(&["ab", "a"]).add("inc({})", |args| Some(Op::Inc(args[0])));
+        let found = set.find_all("xinc(ab)inc(a]inc(a)inc(7");
+        assert_eq!(
+            found,
+            vec![Op::Inc(Operand::Register(0)), Op::Inc(Operand::Register(1))]
+        );
+
+        let set = InstructionSet::new(&[]).add("inc({2})", |args| Some(Op::Inc(args[0])));
+        let found = set.find_all("inc(12)inc(+1)inc(-1)inc(123)inc(a)inc(07)");
+        assert_eq!(found, vec![Op::Inc(Operand::Value(12)), Op::Inc(Operand::Value(7))]);
+    }
+}
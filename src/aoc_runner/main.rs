@@ -13,11 +13,54 @@ struct Args {
 
     #[arg(short, long, default_value_t = -1)]
     part: i32,
+
+    /// Draw frames shown by solutions in the terminal
+    #[arg(long)]
+    visualize: bool,
+
+    /// Pause after every drawn frame, in milliseconds
+    #[arg(long, default_value_t = 50)]
+    frame_delay: u64,
+
+    /// Write frames shown by solutions to a text file
+    #[arg(long)]
+    dump_frames: Option<std::path::PathBuf>,
+
+    /// Save images produced by solutions as PNG files in this directory
+    #[arg(long)]
+    image_dir: Option<std::path::PathBuf>,
+
+    /// Print debug traces of solutions to stderr
+    #[arg(short = 't', long, visible_alias = "verbose")]
+    trace: bool,
+
+    /// Write debug traces to a log file per puzzle in this directory, implies --trace
+    #[arg(long)]
+    trace_dir: Option<std::path::PathBuf>,
 }
 
 fn main() {
     let args = Args::parse();
 
+    if args.visualize || args.dump_frames.is_some() {
+        aoc::viz::configure(aoc::viz::Settings {
+            visualize: args.visualize,
+            frame_delay: std::time::Duration::from_millis(args.frame_delay),
+            dump_file: args.dump_frames,
+        });
+    }
+
+    if let Some(dir) = args.image_dir {
+        aoc::image::configure(dir);
+    }
+
+    if args.trace || args.trace_dir.is_some() {
+        aoc::trace::configure(aoc::trace::Settings {
+            enabled: true,
+            log_dir: args.trace_dir,
+        });
+    }
+
     println!("Let's solve Advent Of Code!");
 
     year_2023::Solver::solve(args.year, args.day, args.part);
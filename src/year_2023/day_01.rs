@@ -9,9 +9,9 @@ pub mod part1 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(input_file_name: &str) -> String {
+        fn solve(input: &str) -> String {
             let mut result: u64 = 0;
-            for line in std::fs::read_to_string(input_file_name).unwrap().lines() {
+            for line in input.lines() {
                 result += get_value_from_line(line);
             }
             result.to_string()
@@ -104,9 +104,9 @@ pub mod part2 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(input_file_name: &str) -> String {
+        fn solve(input: &str) -> String {
             let mut result: u64 = 0;
-            for line in std::fs::read_to_string(input_file_name).unwrap().lines() {
+            for line in input.lines() {
                 result += get_value_from_line(line);
             }
             result.to_string()
@@ -85,9 +85,9 @@ pub mod part1 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
+        fn solve(input: &str) -> String {
             let mut result: u64 = 0;
-            for line in std::fs::read_to_string(file_name).unwrap().lines() {
+            for line in input.lines() {
                 let words: Vec<&str> = line.split(":").collect();
                 assert_eq!(words.len(), 2);
                 let game_index = get_game_index(words[0]);
@@ -131,9 +131,9 @@ pub mod part2 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
+        fn solve(input: &str) -> String {
             let mut result: u64 = 0;
-            for line in std::fs::read_to_string(file_name).unwrap().lines() {
+            for line in input.lines() {
                 let words: Vec<&str> = line.split(":").collect();
                 assert_eq!(words.len(), 2);
                 result += get_powers(words[1]);
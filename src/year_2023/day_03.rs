@@ -28,9 +28,9 @@ impl Matrix {
         self.n_cols
     }
 
-    pub fn read_from_file(file_name: &str) -> Matrix {
+    pub fn from_text(input: &str) -> Matrix {
         let mut result = Matrix::default();
-        for line in std::fs::read_to_string(file_name).unwrap().lines() {
+        for line in input.lines() {
             result.add_line(line.to_owned());
         }
         result
@@ -256,9 +256,9 @@ pub mod part1 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
+        fn solve(input: &str) -> String {
             let mut result: u64 = 0;
-            let matrix = Matrix::read_from_file(file_name);
+            let matrix = Matrix::from_text(input);
 
             for i in 0..matrix.rows() {
                 for j in 0..matrix.cols() {
@@ -295,9 +295,9 @@ pub mod part2 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
+        fn solve(input: &str) -> String {
             let mut result: u64 = 0;
-            let matrix = Matrix::read_from_file(file_name);
+            let matrix = Matrix::from_text(input);
 
             for i in 0..matrix.rows() {
                 for j in 0..matrix.cols() {
@@ -2,7 +2,6 @@ use std::collections::HashSet;
 
 #[derive(Default)]
 struct Card {
-    id: u64,
     numbers: HashSet<u64>,
     winning: HashSet<u64>,
 }
@@ -13,23 +12,12 @@ impl Card {
     }
 
     pub fn parse(line: &str) -> Card {
-        let mut result = Card::default();
-
-        let parts: Vec<&str> = line.split(": ").collect();
-        assert_eq!(2, parts.len());
-
-        let words: Vec<&str> = parts[0].split_ascii_whitespace().collect();
-        assert_eq!(2, words.len());
-        result.id = aoc::parse_or_panic(words[1]);
-
-        let parts: Vec<&str> = parts[1].split(" | ").collect();
-        let winning: Vec<&str> = parts[0].split_ascii_whitespace().collect();
-        let numbers: Vec<&str> = parts[1].split_ascii_whitespace().collect();
-
-        result.winning = winning.iter().map(|x| aoc::parse_or_panic(x)).collect();
-        result.numbers = numbers.iter().map(|x| aoc::parse_or_panic(x)).collect();
-
-        return result;
+        // cards come in order, the id isn't needed
+        let parts = aoc::parse::scan(line, "Card {}: {} | {}").unwrap();
+        Card {
+            winning: aoc::input::ints(parts[1]).collect(),
+            numbers: aoc::input::ints(parts[2]).collect(),
+        }
     }
 }
 
@@ -41,7 +29,6 @@ mod tests {
     fn card_test() {
         let line = "Card   15: 42 5 78 89 | 42 99  1 89";
         let card = Card::parse(&line);
-        assert_eq!(15, card.id);
         assert_eq!(2, card.num_wins());
     }
 }
@@ -58,9 +45,9 @@ pub mod part1 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
+        fn solve(input: &str) -> String {
             let mut result: u64 = 0;
-            for line in std::fs::read_to_string(file_name).unwrap().lines() {
+            for line in input.lines() {
                 let card = Card::parse(line);
                 result += get_score(card.num_wins());
             }
@@ -117,9 +104,9 @@ pub mod part2 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
+        fn solve(input: &str) -> String {
             let mut cards: Vec<Card> = Vec::new();
-            for line in std::fs::read_to_string(file_name).unwrap().lines() {
+            for line in input.lines() {
                 let card = Card::parse(line);
                 cards.push(card);
             }
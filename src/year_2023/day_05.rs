@@ -1,3 +1,5 @@
+use aoc::interval::{Interval, IntervalMap, IntervalSet};
+
 #[derive(Default, Clone)]
 struct Range {
     dest_start: u64,
@@ -6,11 +8,8 @@ struct Range {
 }
 
 impl Range {
-    pub fn transform(self: &Range, num: u64) -> Option<u64> {
-        if num < self.source_start || num > self.source_start + self.length {
-            return None;
-        }
-        Some(num - self.source_start + self.dest_start)
+    fn source(self: &Range) -> Interval<u64> {
+        Interval::from_start_len(self.source_start, self.length)
     }
 }
 
@@ -21,7 +20,7 @@ struct Map {
 
 impl Map {
     pub fn append(self: &mut Map, line: &str) {
-        let values: Vec<u64> = line.split_ascii_whitespace().map(|x| aoc::parse_or_panic(x)).collect();
+        let values: Vec<u64> = aoc::input::ints(line).collect();
         assert_eq!(values.len(), 3);
         self.ranges.push(Range {
             dest_start: values[0],
@@ -30,54 +29,104 @@ impl Map {
         });
     }
 
-    pub fn transform(self: &Map, seed: u64) -> u64 {
+    fn interval_map(self: &Map) -> IntervalMap<u64> {
+        let mut map = IntervalMap::new();
         for range in self.ranges.iter() {
-            if let Some(value) = range.transform(seed) {
-                return value;
-            }
+            map.add(range.source(), range.dest_start);
         }
-        seed
+        map
+    }
+
+    pub fn transform(self: &Map, seed: u64) -> u64 {
+        self.interval_map().map_value(seed)
+    }
+
+    pub fn transform_set(self: &Map, seeds: &IntervalSet<u64>) -> IntervalSet<u64> {
+        self.interval_map().map_set(seeds)
     }
 }
 
 #[derive(Default)]
-struct Data {
+pub struct Data {
     seeds: Vec<u64>,
     maps: Vec<Map>,
 }
 
 impl Data {
-    pub fn from_file(file_name: &str) -> Data {
-        let mut result = Data::default();
-        let mut map = Map::default();
-
-        for line in std::fs::read_to_string(file_name).unwrap().lines() {
-            if line.starts_with("seeds:") {
-                let parts: Vec<&str> = line.split(":").collect();
-                assert_eq!(2, parts.len());
-                result.seeds = parts[1]
-                    .split_ascii_whitespace()
-                    .map(|x| aoc::parse_or_panic(x))
-                    .collect();
-            } else if line.is_empty() {
-            } else if line.as_bytes()[0].is_ascii_digit() {
+    pub fn parse(text: &str) -> Data {
+        let mut paragraphs = aoc::input::paragraphs(text);
+
+        let seeds = paragraphs.next().expect("seeds are missing");
+        assert!(seeds.starts_with("seeds:"));
+        let mut result = Data {
+            seeds: aoc::input::ints(seeds).collect(),
+            ..Default::default()
+        };
+
+        for paragraph in paragraphs {
+            // the first line is a map name
+            let mut map = Map::default();
+            for line in paragraph.lines().skip(1) {
                 map.append(line);
-            } else {
-                if !map.ranges.is_empty() {
-                    result.maps.push(map);
-                    map = Map::default();
-                }
             }
+            result.maps.push(map);
         }
-        result.maps.push(map);
 
         result
     }
 }
 
+#[allow(dead_code)] // used in tests
+fn get_test_input() -> String {
+    [
+        "seeds: 79 14 55 13",
+        "",
+        "seed-to-soil map:",
+        "50 98 2",
+        "52 50 48",
+        "",
+        "soil-to-fertilizer map:",
+        "0 15 37",
+        "37 52 2",
+        "39 0 15",
+        "",
+        "fertilizer-to-water map:",
+        "49 53 8",
+        "0 11 42",
+        "42 0 7",
+        "57 7 4",
+        "",
+        "water-to-light map:",
+        "88 18 7",
+        "18 25 70",
+        "",
+        "light-to-temperature map:",
+        "45 77 23",
+        "81 45 19",
+        "68 64 13",
+        "",
+        "temperature-to-humidity map:",
+        "0 69 1",
+        "1 0 69",
+        "",
+        "humidity-to-location map:",
+        "60 56 37",
+        "56 93 4",
+    ]
+    .join("\n")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc::Day;
+
+    #[test]
+    fn solution_test() {
+        let parsed = Solution::parse(&get_test_input());
+        assert_eq!(Solution::part1(&parsed), "35");
+        assert_eq!(Solution::part2(&parsed), "46");
+    }
 
     #[test]
     fn map_test() {
@@ -87,7 +136,9 @@ mod tests {
         map.append("25 60 9");
 
         assert_eq!(6, map.transform(51));
-        assert_eq!(14, map.transform(59));
+        assert_eq!(13, map.transform(58));
+        // ranges are half-open, the end of one is the start of the next
+        assert_eq!(59, map.transform(59));
         assert_eq!(25, map.transform(60));
         assert_eq!(26, map.transform(61));
     }
@@ -96,146 +147,67 @@ mod tests {
 pub mod part1 {
     use super::*;
 
-    pub struct Puzzle {}
-    impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
-            let mut result: Option<u64> = None;
-
-            let puzzle = Data::from_file(file_name);
-
-            for seed in puzzle.seeds {
-                let mut seed = seed;
-                for map in puzzle.maps.iter() {
-                    seed = map.transform(seed);
-                }
-                match result {
-                    None => result = Some(seed),
-                    Some(value) => result = Some(u64::min(value, seed)),
-                }
-            }
+    #[allow(dead_code)] // used in tests, the runner goes through solve_day
+    pub type Puzzle = aoc::DayPart<Solution, 1>;
 
-            result.unwrap().to_string()
-        }
+    pub(super) fn solve_puzzle(data: &Data) -> u64 {
+        let mut result: Option<u64> = None;
 
-        fn day() -> i32 {
-            5
-        }
-
-        fn part() -> i32 {
-            1
-        }
-
-        fn year() -> i32 {
-            2023
-        }
-    }
-}
-
-pub mod part2 {
-    use super::*;
-    use std::sync::Arc;
-
-    fn solve_interval(mappings: &Vec<Map>, start: u64, len: u64) -> u64 {
-        let mut result = u64::MAX;
-
-        for i in start..=start + len {
-            let mut seed = i;
-            for map in mappings.iter() {
+        for &seed in data.seeds.iter() {
+            let mut seed = seed;
+            for map in data.maps.iter() {
                 seed = map.transform(seed);
             }
-            result = u64::min(result, seed);
-        }
-
-        result
-    }
-
-    fn split_ranges(ranges: &Vec<(u64, u64)>, range_size: u64) -> Vec<(u64, u64)> {
-        let mut result = Vec::new();
-
-        for &(start, length) in ranges.iter() {
-            let mut total_length = 0;
-            let mut current_start = start;
-
-            while total_length < length {
-                let split_length = std::cmp::min(length - total_length, range_size);
-                result.push((current_start, split_length));
-                current_start += split_length + 1;
-                total_length += split_length + 1;
+            match result {
+                None => result = Some(seed),
+                Some(value) => result = Some(u64::min(value, seed)),
             }
         }
 
-        result
+        result.unwrap()
     }
 
-    fn solve_puzzle_parallel(data: &Data) -> u64 {
-        let inputs: Vec<(u64, u64)> = data.seeds.chunks(2).map(|chunk| (chunk[0], chunk[1])).collect();
-        let chunks = split_ranges(&inputs, 25_000_000);
-
-        let n_workers = std::thread::available_parallelism().unwrap().get();
-        let pool = threadpool::ThreadPool::new(n_workers);
-        let (tx, rx) = std::sync::mpsc::channel::<u64>();
-
-        let mappings = Arc::new(data.maps.clone());
-
-        for c in chunks {
-            let tx = tx.clone();
-            let m = Arc::clone(&mappings);
-
-            pool.execute(move || {
-                let result = solve_interval(&m, c.0, c.1);
-                tx.send(result).unwrap();
-            });
-        }
-        pool.join();
-        drop(tx);
-
-        let result = rx.iter().min().unwrap();
-        result
-    }
+    #[cfg(test)]
+    mod tests {
+        use super::*;
 
-    pub struct Puzzle {}
-    impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
-            let data = Data::from_file(file_name);
-            solve_puzzle_parallel(&data).to_string()
+        #[test]
+        fn example_test() {
+            let data = Data::parse(&get_test_input());
+            assert_eq!(35, solve_puzzle(&data));
         }
 
-        fn day() -> i32 {
-            5
+        #[test]
+        fn puzzle_test() {
+            assert_eq!(<Puzzle as aoc::Puzzle>::solve(&get_test_input()), "35");
         }
+    }
+}
 
-        fn part() -> i32 {
-            2
-        }
+pub mod part2 {
+    use super::*;
 
-        fn year() -> i32 {
-            2023
+    #[allow(dead_code)] // used in tests, the runner goes through solve_day
+    pub type Puzzle = aoc::DayPart<Solution, 2>;
+
+    // Seeds are given as (start, length) pairs, so instead of mapping every seed
+    // we map whole intervals splitting them on the map range boundaries
+    pub(super) fn solve_puzzle(data: &Data) -> u64 {
+        let mut seeds: IntervalSet<u64> = data
+            .seeds
+            .chunks(2)
+            .map(|chunk| Interval::from_start_len(chunk[0], chunk[1]))
+            .collect();
+        for map in data.maps.iter() {
+            seeds = map.transform_set(&seeds);
         }
+        seeds.min().unwrap()
     }
 
     #[cfg(test)]
     mod tests {
         use super::*;
 
-        #[test]
-        fn split_ranges_test() {
-            {
-                let input = vec![(1000, 499)];
-                let ranges = split_ranges(&input, 99);
-
-                assert_eq!(vec![(1000, 99), (1100, 99), (1200, 99), (1300, 99), (1400, 99)], ranges);
-            }
-            {
-                let input = vec![(1000, 550)];
-                let ranges = split_ranges(&input, 99);
-
-                assert_eq!(
-                    vec![(1000, 99), (1100, 99), (1200, 99), (1300, 99), (1400, 99), (1500, 50)],
-                    ranges
-                );
-            }
-        }
-
         #[test]
         fn example_test() {
             let mut puzzle = Data::default();
@@ -374,7 +346,43 @@ pub mod part2 {
                 ],
             });
 
-            assert_eq!(46, solve_puzzle_parallel(&puzzle));
+            assert_eq!(46, solve_puzzle(&puzzle));
+        }
+
+        #[test]
+        fn parse_test() {
+            let data = Data::parse(&get_test_input());
+            assert_eq!(46, solve_puzzle(&data));
+        }
+
+        #[test]
+        fn puzzle_test() {
+            assert_eq!(<Puzzle as aoc::Puzzle>::solve(&get_test_input()), "46");
         }
     }
 }
+
+pub struct Solution {}
+impl aoc::Day for Solution {
+    type Parsed = Data;
+
+    fn parse(input: &str) -> Data {
+        Data::parse(input)
+    }
+
+    fn part1(data: &Data) -> String {
+        part1::solve_puzzle(data).to_string()
+    }
+
+    fn part2(data: &Data) -> String {
+        part2::solve_puzzle(data).to_string()
+    }
+
+    fn day() -> i32 {
+        5
+    }
+
+    fn year() -> i32 {
+        2023
+    }
+}
@@ -5,8 +5,8 @@ struct Race {
 }
 
 fn parse_races(time: &str, dist: &str) -> Vec<Race> {
-    let times = time.split_ascii_whitespace().map(|x| aoc::parse_or_panic(x));
-    let dists = dist.split_ascii_whitespace().map(|x| aoc::parse_or_panic(x));
+    let times: Vec<u64> = aoc::input::ints(time).collect();
+    let dists: Vec<u64> = aoc::input::ints(dist).collect();
 
     std::iter::zip(times, dists)
         .map(|(time, dist)| Race { time, dist })
@@ -14,24 +14,15 @@ fn parse_races(time: &str, dist: &str) -> Vec<Race> {
 }
 
 fn parse_single_race(time: &str, dist: &str) -> Race {
-    let mut t: u64 = 0;
-    let mut d: u64 = 0;
-    for c in time.chars() {
-        if c == ' ' {
-            continue;
-        }
-        t *= 10;
-        t += c.to_digit(10).unwrap() as u64;
-    }
-    for c in dist.chars() {
-        if c == ' ' {
-            continue;
-        }
-        d *= 10;
-        d += c.to_digit(10).unwrap() as u64;
+    // the spaces between the numbers are meant to be ignored
+    let time: Vec<u64> = aoc::input::ints(&time.replace(' ', "")).collect();
+    let dist: Vec<u64> = aoc::input::ints(&dist.replace(' ', "")).collect();
+    assert_eq!((time.len(), dist.len()), (1, 1));
+
+    Race {
+        time: time[0],
+        dist: dist[0],
     }
-
-    Race { time: t, dist: d }
 }
 
 fn solve_race(race: &Race) -> u64 {
@@ -94,16 +85,11 @@ pub mod part1 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
-            let handle = std::fs::read_to_string(file_name).unwrap();
-            let lines: Vec<&str> = handle.lines().collect();
+        fn solve(input: &str) -> String {
+            let lines: Vec<&str> = input.lines().collect();
             assert_eq!(2, lines.len());
 
-            let time: Vec<&str> = lines[0].split(":").collect();
-            let dist: Vec<&str> = lines[1].split(":").collect();
-            assert_eq!(2, time.len());
-            assert_eq!(2, dist.len());
-            let races = parse_races(time[1].trim(), dist[1].trim());
+            let races = parse_races(lines[0], lines[1]);
             solve_puzzle(&races).to_string()
         }
 
@@ -142,16 +128,11 @@ pub mod part2 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
-            let handle = std::fs::read_to_string(file_name).unwrap();
-            let lines: Vec<&str> = handle.lines().collect();
+        fn solve(input: &str) -> String {
+            let lines: Vec<&str> = input.lines().collect();
             assert_eq!(2, lines.len());
 
-            let time: Vec<&str> = lines[0].split(":").collect();
-            let dist: Vec<&str> = lines[1].split(":").collect();
-            assert_eq!(2, time.len());
-            assert_eq!(2, dist.len());
-            let race = parse_single_race(time[1].trim(), dist[1].trim());
+            let race = parse_single_race(lines[0], lines[1]);
             solve_race(&race).to_string()
         }
 
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::cmp::Ordering;
 
 #[derive(Debug, Eq, PartialEq)]
 struct Hand {
@@ -14,36 +14,128 @@ impl Hand {
             bid: aoc::parse_or_panic(parts[1]),
         }
     }
+}
+
+// From the weakest to the strongest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Category {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+// A hand falls into the first category whose pattern is a prefix of its card
+// counts sorted in descending order: [3, 2] is a full house, [3] is three of
+// a kind no matter what the other two cards are
+type CategoryRules = &'static [(&'static [usize], Category)];
+
+const CAMEL_CATEGORIES: CategoryRules = &[
+    (&[5], Category::FiveOfAKind),
+    (&[4], Category::FourOfAKind),
+    (&[3, 2], Category::FullHouse),
+    (&[3], Category::ThreeOfAKind),
+    (&[2, 2], Category::TwoPair),
+    (&[2], Category::OnePair),
+    (&[], Category::HighCard),
+];
+
+struct HandRules {
+    // from the weakest to the strongest
+    card_order: &'static str,
+    // cards that pretend to be whatever card makes the hand the strongest,
+    // ties between hands of the same category still use their own rank
+    wildcards: &'static str,
+    hand_size: usize,
+    categories: CategoryRules,
+}
+
+const PART1_RULES: HandRules = HandRules {
+    card_order: "23456789TJQKA",
+    wildcards: "",
+    hand_size: 5,
+    categories: CAMEL_CATEGORIES,
+};
+
+const PART2_RULES: HandRules = HandRules {
+    card_order: "J23456789TQKA",
+    wildcards: "J",
+    hand_size: 5,
+    categories: CAMEL_CATEGORIES,
+};
+
+impl HandRules {
+    fn card_rank(&self, card: char) -> usize {
+        self.card_order
+            .chars()
+            .position(|c| c == card)
+            .unwrap_or_else(|| panic!("Unknown card {}", card))
+    }
 
-    // 6 - Five of a kind
-    // 5 - Four of a kind
-    // 4 - Full house (3 + 2)
-    // 3 - Three of a kind
-    // 2 - Two pair
-    // 1 - One pair
-    // 0 - High card
-    pub fn strength(self: &Hand) -> u64 {
-        let mut counts: HashMap<char, u64> = HashMap::new();
-        for c in self.cards.chars() {
-            *counts.entry(c).or_insert(0) += 1
+    // Counts of every distinct card in descending order. Wildcards join the
+    // largest group, which is always the best use of them for the categories
+    // built from "n of a kind" patterns
+    fn frequencies(&self, cards: &str) -> Vec<usize> {
+        let mut counts = vec![0; self.card_order.len()];
+        let mut wildcard_count = 0;
+        for card in cards.chars() {
+            if self.wildcards.contains(card) {
+                wildcard_count += 1;
+            } else {
+                counts[self.card_rank(card)] += 1;
+            }
         }
-        let scores = freqs_to_tuple(&counts);
-        match scores {
-            (5, _) => 6,
-            (4, _) => 5,
-            (3, 2) => 4,
-            (3, _) => 3,
-            (2, 2) => 2,
-            (2, _) => 1,
-            _ => 0,
+        let mut result: Vec<usize> = counts.into_iter().filter(|&c| c > 0).collect();
+        result.sort_unstable_by(|a, b| b.cmp(a));
+        match result.first_mut() {
+            Some(largest) => *largest += wildcard_count,
+            None => result.push(wildcard_count),
         }
+        result
+    }
+
+    fn classify(&self, cards: &str) -> Category {
+        assert_eq!(cards.chars().count(), self.hand_size, "Wrong hand size: {}", cards);
+        let frequencies = self.frequencies(cards);
+        self.categories
+            .iter()
+            .find(|(pattern, _)| frequencies.starts_with(pattern))
+            .map(|(_, category)| *category)
+            .unwrap_or_else(|| panic!("No category for {}", cards))
     }
+
+    // Category first, then card by card
+    fn cmp(&self, lhs: &Hand, rhs: &Hand) -> Ordering {
+        let ranks = |hand: &Hand| hand.cards.chars().map(|c| self.card_rank(c)).collect::<Vec<_>>();
+        self.classify(&lhs.cards)
+            .cmp(&self.classify(&rhs.cards))
+            .then_with(|| ranks(lhs).cmp(&ranks(rhs)))
+    }
+
+    fn sort(&self, hands: &mut [Hand]) {
+        hands.sort_by(|lhs, rhs| self.cmp(lhs, rhs));
+    }
+}
+
+fn solve_hands(hands: &[Hand]) -> u64 {
+    let mut result: u64 = 0;
+    for (i, hand) in hands.iter().enumerate() {
+        result += hand.bid * (i as u64 + 1);
+    }
+
+    result
 }
 
-fn freqs_to_tuple(freqs: &HashMap<char, u64>) -> (u64, u64) {
-    let mut frequencies: Vec<u64> = freqs.values().cloned().collect();
-    frequencies.sort_by_key(|v| std::cmp::Reverse(*v));
-    (*frequencies.get(0).unwrap_or(&0), *frequencies.get(1).unwrap_or(&0))
+fn solve(input: &str, rules: &HandRules) -> u64 {
+    let mut hands = Vec::new();
+    for line in input.lines() {
+        hands.push(Hand::from_string(line));
+    }
+    rules.sort(&mut hands);
+    solve_hands(&hands)
 }
 
 #[cfg(test)]
@@ -69,93 +161,74 @@ mod tests {
     }
 
     #[test]
-    fn strength_test() {
-        assert_eq!(
-            Hand {
-                cards: "32T3K".to_owned(),
-                bid: 765
-            }
-            .strength(),
-            1
-        );
-        assert_eq!(
-            Hand {
-                cards: "4TT64".to_owned(),
-                bid: 765
-            }
-            .strength(),
-            2
-        );
-        assert_eq!(
-            Hand {
-                cards: "33333".to_owned(),
-                bid: 765
-            }
-            .strength(),
-            6
-        );
+    fn classify_test() {
+        assert_eq!(PART1_RULES.classify("32T3K"), Category::OnePair);
+        assert_eq!(PART1_RULES.classify("4TT64"), Category::TwoPair);
+        assert_eq!(PART1_RULES.classify("33333"), Category::FiveOfAKind);
+        assert_eq!(PART1_RULES.classify("33J33"), Category::FourOfAKind);
+        assert_eq!(PART1_RULES.classify("3K3K3"), Category::FullHouse);
+        assert_eq!(PART1_RULES.classify("23456"), Category::HighCard);
     }
-}
 
-fn solve_hands(hands: &Vec<Hand>) -> u64 {
-    let mut result: u64 = 0;
-    for (i, hand) in hands.iter().enumerate() {
-        result += hand.bid * (i as u64 + 1);
+    #[test]
+    fn custom_rules_test() {
+        let rules = HandRules {
+            card_order: "123",
+            wildcards: "",
+            hand_size: 3,
+            categories: &[(&[3], Category::FiveOfAKind), (&[], Category::HighCard)],
+        };
+        assert_eq!(rules.classify("222"), Category::FiveOfAKind);
+        assert_eq!(rules.classify("223"), Category::HighCard);
     }
 
-    result
-}
-
-pub mod part1 {
-    use super::*;
-
-    fn get_card_score(card: char) -> u64 {
-        match card {
-            'A' => 13,
-            'K' => 12,
-            'Q' => 11,
-            'J' => 10,
-            'T' => 9,
-            '9' => 8,
-            '8' => 7,
-            '7' => 6,
-            '6' => 5,
-            '5' => 4,
-            '4' => 3,
-            '3' => 2,
-            '2' => 1,
-            _ => 0,
-        }
+    // Tries every possible replacement of every wildcard
+    fn brute_force_classify(rules: &HandRules, cards: &str) -> Category {
+        let Some(pos) = cards.chars().position(|c| rules.wildcards.contains(c)) else {
+            return rules.classify(cards);
+        };
+        rules
+            .card_order
+            .chars()
+            .filter(|c| !rules.wildcards.contains(*c))
+            .map(|replacement| {
+                let mut replaced: Vec<char> = cards.chars().collect();
+                replaced[pos] = replacement;
+                brute_force_classify(rules, &replaced.into_iter().collect::<String>())
+            })
+            .max()
+            .unwrap()
     }
 
-    fn cmp(lhs: &Hand, rhs: &Hand) -> std::cmp::Ordering {
-        let result = lhs.strength().cmp(&rhs.strength());
-        match result {
-            std::cmp::Ordering::Equal => {
-                for i in 0..5 {
-                    let lhs_score = get_card_score(lhs.cards.as_bytes()[i] as char);
-                    let rhs_score = get_card_score(rhs.cards.as_bytes()[i] as char);
-                    if lhs_score == rhs_score {
-                        continue;
-                    }
-                    let result = lhs_score.cmp(&rhs_score);
-                    return result;
-                }
-                std::cmp::Ordering::Equal
-            }
-            _ => result,
+    #[test]
+    fn wildcards_fuzz_test() {
+        // a fixed LCG keeps the test reproducible, jokers are drawn a lot
+        // more often than the other cards to get interesting hands
+        let mut seed: u64 = 7;
+        let mut next = move || {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 33) as usize
+        };
+        let cards: Vec<char> = "J23456789TQKAJJJ".chars().collect();
+        for _ in 0..200 {
+            let hand: String = (0..5).map(|_| cards[next() % cards.len()]).collect();
+            assert_eq!(
+                PART2_RULES.classify(&hand),
+                brute_force_classify(&PART2_RULES, &hand),
+                "{}",
+                hand
+            );
         }
     }
+}
+
+pub mod part1 {
+    use super::*;
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
-            let mut hands = Vec::new();
-            for line in std::fs::read_to_string(file_name).unwrap().lines() {
-                hands.push(Hand::from_string(line));
-            }
-            hands.sort_unstable_by(|lhs, rhs| cmp(&lhs, &rhs));
-            solve_hands(&hands).to_string()
+        fn solve(input: &str) -> String {
+            solve(input, &PART1_RULES).to_string()
         }
 
         fn day() -> i32 {
@@ -175,6 +248,10 @@ pub mod part1 {
     mod tests {
         use super::*;
 
+        fn cmp(lhs: &Hand, rhs: &Hand) -> Ordering {
+            PART1_RULES.cmp(lhs, rhs)
+        }
+
         #[test]
         fn hand_cmp_test() {
             assert_eq!(
@@ -217,116 +294,10 @@ pub mod part1 {
 pub mod part2 {
     use super::*;
 
-    fn get_card_score(card: char) -> u64 {
-        match card {
-            'A' => 13,
-            'K' => 12,
-            'Q' => 11,
-            'T' => 10,
-            '9' => 9,
-            '8' => 8,
-            '7' => 7,
-            '6' => 6,
-            '5' => 5,
-            '4' => 4,
-            '3' => 3,
-            '2' => 2,
-            'J' => 1,
-            _ => 0,
-        }
-    }
-
-    fn preprocess_hand(hand: &str) -> String {
-        // create a frequency map of all cards except jokers
-        let mut counts: HashMap<char, u64> = HashMap::new();
-        for c in hand.chars() {
-            if c == 'J' {
-                continue;
-            }
-            *counts.entry(c).or_insert(0) += 1
-        }
-
-        // maps frequency to a list of characters
-        let mut freq_map: HashMap<u64, Vec<char>> = HashMap::new();
-        for (char, freq) in counts.iter() {
-            freq_map.entry(*freq).or_insert(vec![]).push(*char);
-        }
-
-        // if the hand is all jokers replace them with aces and return
-        if freq_map.is_empty() {
-            return "AAAAA".to_owned();
-        }
-
-        let mut result = hand.to_owned();
-
-        // loop through all frequencies from highest to lowest and get
-        // the highest card from the first non-empty stack
-        for i in (1..=5).rev() {
-            if let Some(cards) = freq_map.get(&i) {
-                if cards.is_empty() {
-                    continue;
-                }
-                let card = get_highest_card(&cards);
-                result = result.replace('J', &card.to_string());
-                return result;
-            }
-        }
-        unreachable!();
-    }
-
-    fn cmp(lhs: &Hand, rhs: &Hand) -> std::cmp::Ordering {
-        let joked_lhs = Hand {
-            cards: preprocess_hand(&lhs.cards),
-            bid: 0,
-        };
-        let joked_rhs = Hand {
-            cards: preprocess_hand(&rhs.cards),
-            bid: 0,
-        };
-
-        // first we compare cards after replacing joker, then if it's a tie, fallback to
-        // comparing the original cards
-        let result = joked_lhs.strength().cmp(&joked_rhs.strength());
-        match result {
-            std::cmp::Ordering::Equal => {
-                for i in 0..5 {
-                    let lhs_score = get_card_score(lhs.cards.as_bytes()[i] as char);
-                    let rhs_score = get_card_score(rhs.cards.as_bytes()[i] as char);
-                    if lhs_score == rhs_score {
-                        continue;
-                    }
-                    let result = lhs_score.cmp(&rhs_score);
-                    return result;
-                }
-                std::cmp::Ordering::Equal
-            }
-            _ => result,
-        }
-    }
-
-    fn get_highest_card(cards: &Vec<char>) -> char {
-        if cards.len() == 1 {
-            return cards[0];
-        }
-        // screw it, I'm going dirty
-        for c in "AKQT98765432J".as_bytes() {
-            let c = *c as char;
-            if cards.contains(&c) {
-                return c;
-            }
-        }
-        unreachable!();
-    }
-
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
-            let mut hands = Vec::new();
-            for line in std::fs::read_to_string(file_name).unwrap().lines() {
-                hands.push(Hand::from_string(line));
-            }
-            hands.sort_unstable_by(|lhs, rhs| cmp(&lhs, &rhs));
-            solve_hands(&hands).to_string()
+        fn solve(input: &str) -> String {
+            solve(input, &PART2_RULES).to_string()
         }
 
         fn day() -> i32 {
@@ -347,17 +318,24 @@ pub mod part2 {
         use super::*;
 
         #[test]
-        fn preprocess_test() {
-            assert_eq!("KTTTT", preprocess_hand("KTJJT"));
-            assert_eq!("23455", preprocess_hand("2345J"));
-            assert_eq!("22333", preprocess_hand("2233J"));
-            assert_eq!("AAAAA", preprocess_hand("JJJJJ"));
-            assert_eq!("QQ2Q2", preprocess_hand("JQ2Q2"));
+        fn classify_test() {
+            assert_eq!(PART2_RULES.classify("KTJJT"), Category::FourOfAKind);
+            assert_eq!(PART2_RULES.classify("2345J"), Category::OnePair);
+            assert_eq!(PART2_RULES.classify("2233J"), Category::FullHouse);
+            assert_eq!(PART2_RULES.classify("JJJJJ"), Category::FiveOfAKind);
+            assert_eq!(PART2_RULES.classify("JQ2Q2"), Category::FullHouse);
+        }
+
+        #[test]
+        fn hand_cmp_test() {
+            let cmp = |lhs: &str, rhs: &str| PART2_RULES.cmp(&Hand::from_string(lhs), &Hand::from_string(rhs));
+            // both are four of a kind, a joker is the weakest card
+            assert_eq!(cmp("JKKK2 1", "QQQQ2 1"), Ordering::Less);
+            assert_eq!(cmp("T55J5 684", "QQQJA 483"), Ordering::Less);
         }
 
         #[test]
         fn solve_test() {
-            // here we sort inside preprocess, so this is in arbitrary order
             let mut hands = vec![
                 Hand::from_string("32T3K 765"),
                 Hand::from_string("KTJJT 220"),
@@ -365,7 +343,7 @@ pub mod part2 {
                 Hand::from_string("T55J5 684"),
                 Hand::from_string("QQQJA 483"),
             ];
-            hands.sort_unstable_by(|lhs, rhs| cmp(&lhs, &rhs));
+            PART2_RULES.sort(&mut hands);
             assert_eq!(5905, solve_hands(&hands));
         }
     }
@@ -7,20 +7,14 @@ struct Desert {
 }
 
 fn parse_node(line: &str) -> (&str, &str, &str) {
-    let parts: Vec<&str> = line.split_whitespace().collect();
-
-    assert_eq!(parts.len(), 4);
-    (
-        parts[0],
-        parts[2].trim_matches(|c| c == '(' || c == ')' || c == ','),
-        parts[3].trim_matches(|c| c == '(' || c == ')' || c == ','),
-    )
+    let parts = aoc::parse::scan(line, "{} = ({}, {})").unwrap();
+    (parts[0], parts[1], parts[2])
 }
 
-fn load_desert_from_file(file_name: &str) -> Desert {
+fn parse_desert(input: &str) -> Desert {
     let mut nodes = HashMap::new();
     let mut path = String::new();
-    for line in std::fs::read_to_string(file_name).unwrap().lines() {
+    for line in input.lines() {
         if line.is_empty() {
             continue;
         }
@@ -52,8 +46,8 @@ pub mod part1 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
-            let desert = load_desert_from_file(file_name);
+        fn solve(input: &str) -> String {
+            let desert = parse_desert(input);
 
             let mut i = 0;
             let mut result = 0;
@@ -93,22 +87,10 @@ pub mod part1 {
 pub mod part2 {
     use super::*;
 
-    fn gcd(a: u64, b: u64) -> u64 {
-        if b == 0 {
-            a
-        } else {
-            gcd(b, a % b)
-        }
-    }
-
-    fn lcm(numbers: &[u64]) -> u64 {
-        numbers.iter().fold(1, |acc, &x| acc * x / gcd(acc, x))
-    }
-
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
-            let desert = load_desert_from_file(file_name);
+        fn solve(input: &str) -> String {
+            let desert = parse_desert(input);
 
             let mut curr_nodes = Vec::new();
             for (key, _) in desert.nodes.iter() {
@@ -146,7 +128,7 @@ pub mod part2 {
                     i = (i + 1) % desert.path.len();
                 }
             }
-            lcm(&lengths).to_string()
+            aoc::math::lcm_of(&lengths).to_string()
         }
 
         fn day() -> i32 {
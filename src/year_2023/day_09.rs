@@ -1,8 +1,8 @@
 use aoc::parse_or_panic;
 
-fn parse_input_file(file_name: &str) -> Vec<Vec<i64>> {
+fn parse_input(input: &str) -> Vec<Vec<i64>> {
     let mut result = Vec::new();
-    for line in std::fs::read_to_string(file_name).unwrap().lines() {
+    for line in input.lines() {
         let data: Vec<i64> = line.split_ascii_whitespace().map(|v| parse_or_panic(v)).collect();
         result.push(data);
     }
@@ -26,8 +26,8 @@ pub mod part1 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
-            let lines = parse_input_file(file_name);
+        fn solve(input: &str) -> String {
+            let lines = parse_input(input);
             let mut result = 0;
             for line in lines.iter() {
                 result += solve_line(&line);
@@ -75,8 +75,8 @@ pub mod part2 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
-            let lines = parse_input_file(file_name);
+        fn solve(input: &str) -> String {
+            let lines = parse_input(input);
             let mut result = 0;
             for line in lines.iter() {
                 result += solve_line(&line);
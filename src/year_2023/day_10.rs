@@ -7,11 +7,11 @@ struct Map {
 }
 
 impl Map {
-    fn from_file(file_name: &str) -> Map {
+    fn from_text(input: &str) -> Map {
         let mut nodes: Vec<String> = Vec::new();
         let mut start = Point::default();
         let mut row = 0;
-        for line in std::fs::read_to_string(file_name).unwrap().lines() {
+        for line in input.lines() {
             if let Some(column) = line.as_bytes().iter().position(|x| *x == 'S' as u8) {
                 start.row = row;
                 start.col = column as u64;
@@ -128,29 +128,6 @@ impl Map {
         assert_eq!(2, result.len());
         (result[0], result[1])
     }
-
-    fn get_start_pipe(self: &Map) -> char {
-        let (p1, p2) = self.get_possible_paths_from_start();
-        let r1 = p1.row as i64 - self.start.row as i64;
-        let c1 = p1.col as i64 - self.start.col as i64;
-        let r2 = p2.row as i64 - self.start.row as i64;
-        let c2 = p2.col as i64 - self.start.col as i64;
-
-        match (r1, c1, r2, c2) {
-            (0, -1, 0, 1) => '-',
-            (-1, 0, 1, 0) => '|',
-            (1, 0, 0, 1) => 'F',
-            (0, -1, 1, 0) => '7',
-            (0, -1, -1, 0) => 'J',
-            (-1, 0, 0, 1) => 'L',
-            _ => unreachable!(),
-        }
-    }
-
-    #[allow(dead_code)] // used in tests
-    fn assign(self: &mut Map, row: usize, col: usize, c: char) {
-        self.nodes[row].replace_range(col..col + 1, &c.to_string());
-    }
 }
 
 #[cfg(test)]
@@ -181,12 +158,16 @@ mod tests {
     }
 }
 
-fn traverse(map: &Map) -> std::collections::HashSet<Point> {
+// Returns loop points in the order they are traversed starting from 'S'
+fn traverse(map: &Map) -> Vec<Point> {
     let mut visited = std::collections::HashSet::new();
+    let mut path = Vec::new();
 
     let (mut p, _) = map.get_possible_paths_from_start();
     visited.insert(map.start);
+    path.push(map.start);
     visited.insert(p);
+    path.push(p);
 
     loop {
         let (p1, p2) = map.get_possible_paths(&p);
@@ -195,14 +176,14 @@ fn traverse(map: &Map) -> std::collections::HashSet<Point> {
         }
 
         if visited.contains(&p1) {
-            visited.insert(p2.clone());
             p = p2;
         } else if visited.contains(&p2) {
-            visited.insert(p1.clone());
             p = p1;
         }
+        visited.insert(p);
+        path.push(p);
     }
-    visited
+    path
 }
 
 pub mod part1 {
@@ -216,8 +197,8 @@ pub mod part1 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
-            let map = Map::from_file(file_name);
+        fn solve(input: &str) -> String {
+            let map = Map::from_text(input);
             solve(&map).to_string()
         }
 
@@ -251,60 +232,18 @@ pub mod part1 {
 pub mod part2 {
     use super::*;
 
-    fn is_inside(intersections: &Vec<char>) -> bool {
-        let mut s: String = intersections.into_iter().collect();
-        s = s.replace("LJ", "");
-        s = s.replace("F7", "");
-
-        s = s.replace("L7", "|");
-        s = s.replace("FJ", "|");
-        s.len() % 2 == 1
-    }
-
-    // Here we go over all Points, skip ones that are the loop itself, and for each Point
-    // count how many "edges" of the loop we cross going from this node to the right.
-    // The trick is to correctly handle going by the edge (see is_inside function for this).
-    // Note that we need to count not only blank Points, but pipes that are not the part
-    // of the main loop as well.
+    // Loop points are the vertices of a lattice polygon, so the number of
+    // enclosed tiles is the number of its interior points (Pick's theorem)
     fn solve(map: &Map) -> u64 {
         assert!(!map.nodes.is_empty());
-        let visited = traverse(map);
-        let mut result = 0;
-        let accepted_pipes = vec!['|', 'L', 'J', '7', 'F', 'S'];
-        for (row, line) in map.nodes.iter().enumerate() {
-            for (col, _) in line.as_bytes().iter().enumerate() {
-                if visited.contains(&Point {
-                    row: row as u64,
-                    col: col as u64,
-                }) {
-                    continue;
-                }
-                let mut intersections = Vec::new();
-                for c in col..line.len() {
-                    let mut value = line.as_bytes()[c] as char;
-                    if visited.contains(&Point {
-                        row: row as u64,
-                        col: c as u64,
-                    }) && accepted_pipes.contains(&value)
-                    {
-                        if value == 'S' {
-                            value = map.get_start_pipe();
-                        }
-                        intersections.push(value);
-                    }
-                }
-                if is_inside(&intersections) {
-                    result += 1;
-                }
-            }
-        }
-        result
+        let path = traverse(map);
+        aoc::geometry::interior_points_count(&path) as u64
     }
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
-            let map = Map::from_file(file_name);
+        fn solve(input: &str) -> String {
+            let map = Map::from_text(input);
             solve(&map).to_string()
         }
 
@@ -357,40 +296,4 @@ pub mod part2 {
 
         assert_eq!(8, solve(&map));
     }
-
-    #[test]
-    fn is_inside_test() {
-        assert_eq!(true, is_inside(&vec!['|']));
-        assert_eq!(false, is_inside(&vec!['|', '|']));
-        assert_eq!(false, is_inside(&vec!['F', '7', '|', '|']));
-        assert_eq!(false, is_inside(&vec!['F', 'J', '|']));
-    }
-
-    #[test]
-    fn get_start_pipe_test() {
-        use crate::day_10::Point;
-        let mut map = Map::default();
-
-        let original_map = vec![
-            ".....".to_owned(),
-            ".F-7.".to_owned(),
-            ".|.|.".to_owned(),
-            ".L-J.".to_owned(),
-            ".....".to_owned(),
-        ];
-
-        for (row, line) in original_map.iter().enumerate() {
-            for (col, tile) in line.chars().enumerate() {
-                if tile != '.' {
-                    map.nodes = original_map.clone();
-                    map.start = Point {
-                        row: row as u64,
-                        col: col as u64,
-                    };
-                    map.assign(row, col, 'S');
-                    assert_eq!(tile, map.get_start_pipe());
-                }
-            }
-        }
-    }
 }
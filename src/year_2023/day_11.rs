@@ -3,9 +3,9 @@ struct Universe {
 }
 
 impl Universe {
-    fn from_file(file_name: &str) -> Universe {
+    fn from_text(input: &str) -> Universe {
         let mut galaxies: Vec<String> = Vec::new();
-        for line in std::fs::read_to_string(file_name).unwrap().lines() {
+        for line in input.lines() {
             galaxies.push(line.to_owned());
         }
         Universe { galaxies: galaxies }
@@ -152,8 +152,8 @@ pub mod part1 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
-            let mut universe = Universe::from_file(file_name);
+        fn solve(input: &str) -> String {
+            let mut universe = Universe::from_text(input);
             universe.expand();
             solve(&universe, 2).to_string()
         }
@@ -177,8 +177,8 @@ pub mod part2 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
-            let mut universe = Universe::from_file(file_name);
+        fn solve(input: &str) -> String {
+            let mut universe = Universe::from_text(input);
             universe.expand();
             solve(&universe, 1000000).to_string()
         }
@@ -1,4 +1,4 @@
-#[derive(Default)]
+#[derive(Default, Clone, PartialEq, Eq, Hash)]
 struct Field {
     rocks: Vec<Vec<char>>,
 }
@@ -255,9 +255,9 @@ pub mod part1 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
+        fn solve(input: &str) -> String {
             let mut field = Field::default();
-            for line in std::fs::read_to_string(file_name).unwrap().lines() {
+            for line in input.lines() {
                 field.rocks.push(line.chars().collect());
             }
             solve_field(&field).to_string()
@@ -280,68 +280,34 @@ pub mod part1 {
 pub mod part2 {
     use super::*;
 
-    fn tilt_field(field: &mut Field) -> (u64, u64, u64, u64) {
+    fn tilt_field(field: &mut Field) {
         field.tilt_north();
-        let a = get_load_north(field);
-
         field.tilt_west();
-        let b = get_load_north(field);
-
         field.tilt_south();
-        let c = get_load_north(field);
-
         field.tilt_east();
-        let d = get_load_north(field);
-
-        (a, b, c, d)
-    }
-
-    // to detect it as a cycle we need to make sure we have two consecutive matched lines
-    // it means that we need to be off by one for all further calculations
-    fn get_cycle(history: &Vec<(u64, u64, u64, u64)>, current_step: &(u64, u64, u64, u64)) -> Option<usize> {
-        if history.is_empty() {
-            return None;
-        }
-        if let Some(second_position) = history.iter().position(|item| item == current_step) {
-            if second_position == 0 {
-                return None;
-            }
-            if history[second_position - 1] == *history.last().unwrap() {
-                return Some(second_position - 1);
-            }
-        }
-        return None;
     }
 
-    fn get_wrapped_index(cycle_start: usize, current_step: usize, n: usize) -> usize {
-        let cycle_len = current_step as usize - cycle_start - 1;
-        let index = (n - cycle_start) % cycle_len + cycle_start;
-        return index;
-    }
-
-    fn solve_field(field: &mut Field) -> u64 {
-        let mut history = Vec::new();
-        let n = 1_000_000_000;
-        for i in 0..n {
-            let step_result = tilt_field(field);
-            if let Some(cycle_start) = get_cycle(&history, &step_result) {
-                // it's 0-based, so we need to find a value on nth - 1 step. damn you off-by-one
-                let index = get_wrapped_index(cycle_start, i, n - 1);
-                return history[index].3;
-            }
-            history.push(step_result);
-        }
+    fn solve_field(field: &Field) -> u64 {
+        let field = aoc::cycle::state_after_n_steps(
+            field.clone(),
+            |field| {
+                let mut field = field.clone();
+                tilt_field(&mut field);
+                field
+            },
+            1_000_000_000,
+        );
         get_load_north(&field)
     }
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
+        fn solve(input: &str) -> String {
             let mut field = Field::default();
-            for line in std::fs::read_to_string(file_name).unwrap().lines() {
+            for line in input.lines() {
                 field.rocks.push(line.chars().collect());
             }
-            solve_field(&mut field).to_string()
+            solve_field(&field).to_string()
         }
 
         fn day() -> i32 {
@@ -412,48 +378,6 @@ pub mod part2 {
             assert_eq!("#.OOO#...O".chars().collect::<Vec<char>>(), field.rocks[9]);
         }
 
-        #[test]
-        fn get_cycle_test() {
-            let mut history = Vec::new();
-
-            history.push((136, 136, 87, 87));
-            history.push((129, 129, 69, 69));
-            history.push((114, 114, 69, 69));
-            history.push((110, 110, 69, 69));
-            history.push((110, 110, 65, 65));
-            history.push((105, 105, 64, 64));
-
-            assert_eq!(None, get_cycle(&history, &(103, 103, 65, 65)));
-            history.push((103, 103, 65, 65));
-
-            assert_eq!(None, get_cycle(&history, &(106, 106, 63, 63)));
-            history.push((106, 106, 63, 63));
-
-            assert_eq!(None, get_cycle(&history, &(111, 111, 68, 68)));
-            history.push((111, 111, 68, 68));
-
-            assert_eq!(None, get_cycle(&history, &(114, 114, 69, 69)));
-            history.push((114, 114, 69, 69));
-
-            assert_eq!(Some(2), get_cycle(&history, &(110, 110, 69, 69)));
-        }
-
-        #[test]
-        fn get_wrapped_index_test() {
-            assert_eq!(6, get_wrapped_index(2, 10, 20));
-            assert_eq!(7, get_wrapped_index(2, 10, 21));
-            assert_eq!(8, get_wrapped_index(2, 10, 22));
-            assert_eq!(2, get_wrapped_index(2, 10, 23));
-
-            assert_eq!(3, get_wrapped_index(2, 10, 24));
-            assert_eq!(4, get_wrapped_index(2, 10, 25));
-            assert_eq!(5, get_wrapped_index(2, 10, 26));
-            assert_eq!(6, get_wrapped_index(2, 10, 27));
-            assert_eq!(7, get_wrapped_index(2, 10, 28));
-            assert_eq!(8, get_wrapped_index(2, 10, 29));
-            assert_eq!(2, get_wrapped_index(2, 10, 30));
-        }
-
         #[test]
         fn solve_test() {
             let mut field = Field::default();
@@ -468,7 +392,7 @@ pub mod part2 {
             field.rocks.push("#....###..".chars().collect());
             field.rocks.push("#OO..#....".chars().collect());
 
-            assert_eq!(64, solve_field(&mut field));
+            assert_eq!(64, solve_field(&field));
         }
     }
 }
@@ -1,9 +1,9 @@
 use std::ops::Add;
 
-fn parse_input(file_name: &str) -> Vec<String> {
+fn parse_input(input: &str) -> Vec<String> {
     let mut s = String::default();
 
-    for line in std::fs::read_to_string(file_name).unwrap().lines() {
+    for line in input.lines() {
         s = s.add(line);
     }
 
@@ -44,8 +44,8 @@ pub mod part1 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
-            let strings = parse_input(file_name);
+        fn solve(input: &str) -> String {
+            let strings = parse_input(input);
             let mut result = 0;
 
             for s in strings.iter() {
@@ -153,8 +153,8 @@ pub mod part2 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
-            let strings = parse_input(file_name);
+        fn solve(input: &str) -> String {
+            let strings = parse_input(input);
             solve(&strings).to_string()
         }
 
@@ -1,9 +1,9 @@
-use aoc::Direction;
+use aoc::viz::{Cell, Color, Frame};
+use aoc::{Direction, DirectionGrid};
 
 #[derive(Debug, Default, Clone)]
 struct Node {
     kind: char,
-    energized: u32,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -20,9 +20,8 @@ struct Beam {
 }
 
 impl Field {
-    fn from_file(file_name: &str) -> Field {
-        let file_content = std::fs::read_to_string(file_name).unwrap();
-        let lines = file_content.lines().collect::<Vec<_>>();
+    fn from_text(input: &str) -> Field {
+        let lines = input.lines().collect::<Vec<_>>();
         Field::from_lines(&lines)
     }
 
@@ -32,10 +31,7 @@ impl Field {
             let mut line = Vec::with_capacity(s.len());
             result.column_count = s.len();
             for c in s.as_bytes() {
-                line.push(Node {
-                    kind: *c as char,
-                    energized: 0,
-                })
+                line.push(Node { kind: *c as char })
             }
             result.nodes.push(line);
         }
@@ -43,15 +39,55 @@ impl Field {
         result
     }
 
-    fn traverse(self: &mut Field, mut beams: Vec<Beam>) {
+    // Mirrors and splitters, energized nodes are highlighted and the beams
+    // are drawn over them
+    fn frame(self: &Field, energized: &DirectionGrid, beams: &[Beam]) -> Frame {
+        let mut result = Frame::with_size(self.nodes.len(), self.column_count, Cell::new('.'));
+        for (row, line) in self.nodes.iter().enumerate() {
+            for (col, node) in line.iter().enumerate() {
+                let mut cell = Cell::new(node.kind);
+                if energized.get(row, col) != 0 {
+                    cell = cell.fg(Color::Black).bg(Color::Yellow);
+                }
+                result.set(
+                    &aoc::Point {
+                        row: row as i32,
+                        col: col as i32,
+                    },
+                    cell,
+                );
+            }
+        }
+        for beam in beams.iter() {
+            let point = aoc::Point {
+                row: beam.row,
+                col: beam.col,
+            };
+            result.set(&point, Cell::new('@').fg(Color::Red).bg(Color::Yellow));
+        }
+        result
+    }
+
+    // Returns directions of the beams that went through every node
+    fn traverse(self: &Field, beams: Vec<Beam>) -> DirectionGrid {
+        self.traverse_with(beams, |_, _| {})
+    }
+
+    // Same as traverse(), on_step is called with the energized nodes and
+    // the beams after every step
+    fn traverse_with<F>(self: &Field, mut beams: Vec<Beam>, mut on_step: F) -> DirectionGrid
+    where
+        F: FnMut(&DirectionGrid, &[Beam]),
+    {
         assert!(!self.nodes.is_empty());
+        let mut energized = DirectionGrid::with_size(self.nodes.len(), self.column_count);
 
         while !beams.is_empty() {
             // energize fields under beams
             for beam in beams.iter() {
-                self.nodes[beam.row as usize][beam.col as usize].energized =
-                    self.nodes[beam.row as usize][beam.col as usize].energized | beam.direction as u32;
+                energized.set(beam.row as usize, beam.col as usize, beam.direction);
             }
+            on_step(&energized, &beams);
 
             let mut next_beams: Vec<Beam> = Vec::with_capacity(beams.capacity());
 
@@ -196,24 +232,13 @@ impl Field {
             });
 
             // remove all beams that are going over the same path
-            next_beams
-                .retain(|beam| self.nodes[beam.row as usize][beam.col as usize].energized & beam.direction as u32 == 0);
+            next_beams.retain(|beam| !energized.test(beam.row as usize, beam.col as usize, beam.direction));
 
             // reassign beams
             beams = next_beams;
         }
-    }
 
-    fn score(self: &Field) -> u64 {
-        let mut result = 0;
-        for line in self.nodes.iter() {
-            for node in line.iter() {
-                if node.energized != 0 {
-                    result += 1;
-                }
-            }
-        }
-        result
+        energized
     }
 }
 
@@ -222,8 +247,8 @@ pub mod part1 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
-            let mut field = Field::from_file(file_name);
+        fn solve(input: &str) -> String {
+            let field = Field::from_text(input);
 
             let beams = vec![Beam {
                 row: 0,
@@ -231,8 +256,13 @@ pub mod part1 {
                 direction: Direction::East,
             }];
 
-            field.traverse(beams);
-            field.score().to_string()
+            let visualize = aoc::viz::is_enabled();
+            let energized = field.traverse_with(beams, |energized, beams| {
+                if visualize {
+                    aoc::viz::show(&field.frame(energized, beams));
+                }
+            });
+            energized.count().to_string()
         }
 
         fn day() -> i32 {
@@ -281,76 +311,76 @@ pub mod part1 {
         #[test]
         fn traverse_east_simple_test() {
             let lines = vec!["....", "....", "....", "...."];
-            let mut field = Field::from_lines(&lines);
+            let field = Field::from_lines(&lines);
             let beams = vec![Beam {
                 row: 0,
                 col: 0,
                 direction: Direction::East,
             }];
 
-            field.traverse(beams);
+            let energized = field.traverse(beams);
 
-            assert_eq!(field.nodes[0][0].energized, 0x8);
-            assert_eq!(field.nodes[0][1].energized, 0x8);
-            assert_eq!(field.nodes[0][2].energized, 0x8);
-            assert_eq!(field.nodes[0][3].energized, 0x8);
+            assert_eq!(energized.get(0, 0), 0x8);
+            assert_eq!(energized.get(0, 1), 0x8);
+            assert_eq!(energized.get(0, 2), 0x8);
+            assert_eq!(energized.get(0, 3), 0x8);
 
             for i in 1..4 as usize {
-                assert_eq!(field.nodes[i][0].energized, 0);
-                assert_eq!(field.nodes[i][1].energized, 0);
-                assert_eq!(field.nodes[i][2].energized, 0);
-                assert_eq!(field.nodes[i][3].energized, 0);
+                assert_eq!(energized.get(i, 0), 0);
+                assert_eq!(energized.get(i, 1), 0);
+                assert_eq!(energized.get(i, 2), 0);
+                assert_eq!(energized.get(i, 3), 0);
             }
         }
 
         #[test]
         fn traverse_south_simple_test() {
             let lines = vec!["....", "....", "....", "...."];
-            let mut field = Field::from_lines(&lines);
+            let field = Field::from_lines(&lines);
             let beams = vec![Beam {
                 row: 0,
                 col: 1, // Starting from the second column
                 direction: Direction::South,
             }];
 
-            field.traverse(beams);
+            let energized = field.traverse(beams);
 
-            assert_eq!(field.nodes[0][1].energized, 0x4);
-            assert_eq!(field.nodes[1][1].energized, 0x4);
-            assert_eq!(field.nodes[2][1].energized, 0x4);
-            assert_eq!(field.nodes[3][1].energized, 0x4);
+            assert_eq!(energized.get(0, 1), 0x4);
+            assert_eq!(energized.get(1, 1), 0x4);
+            assert_eq!(energized.get(2, 1), 0x4);
+            assert_eq!(energized.get(3, 1), 0x4);
 
             for i in 0..4 as usize {
-                assert_eq!(field.nodes[i][0].energized, 0);
-                assert_eq!(field.nodes[i][2].energized, 0);
-                assert_eq!(field.nodes[i][3].energized, 0);
+                assert_eq!(energized.get(i, 0), 0);
+                assert_eq!(energized.get(i, 2), 0);
+                assert_eq!(energized.get(i, 3), 0);
             }
         }
 
         #[test]
         fn traverse_west_simple_test() {
             let lines = vec!["....", "....", "....", "...."];
-            let mut field = Field::from_lines(&lines);
+            let field = Field::from_lines(&lines);
             let beams = vec![Beam {
                 row: 2, // Starting from the third row
                 col: 3, // Starting from the fourth column
                 direction: Direction::West,
             }];
 
-            field.traverse(beams);
+            let energized = field.traverse(beams);
 
-            assert_eq!(field.nodes[2][3].energized, 0x2); // Energized by westward beam
-            assert_eq!(field.nodes[2][2].energized, 0x2);
-            assert_eq!(field.nodes[2][1].energized, 0x2);
-            assert_eq!(field.nodes[2][0].energized, 0x2);
+            assert_eq!(energized.get(2, 3), 0x2); // Energized by westward beam
+            assert_eq!(energized.get(2, 2), 0x2);
+            assert_eq!(energized.get(2, 1), 0x2);
+            assert_eq!(energized.get(2, 0), 0x2);
 
             // Ensure other nodes in the grid are not energized
             for i in 0..4 as usize {
                 if i != 2 {
-                    assert_eq!(field.nodes[i][0].energized, 0);
-                    assert_eq!(field.nodes[i][1].energized, 0);
-                    assert_eq!(field.nodes[i][2].energized, 0);
-                    assert_eq!(field.nodes[i][3].energized, 0);
+                    assert_eq!(energized.get(i, 0), 0);
+                    assert_eq!(energized.get(i, 1), 0);
+                    assert_eq!(energized.get(i, 2), 0);
+                    assert_eq!(energized.get(i, 3), 0);
                 }
             }
         }
@@ -358,25 +388,25 @@ pub mod part1 {
         #[test]
         fn traverse_north_simple_test() {
             let lines = vec!["....", "....", "....", "...."];
-            let mut field = Field::from_lines(&lines);
+            let field = Field::from_lines(&lines);
             let beams = vec![Beam {
                 row: 3, // Starting from the bottom row (fourth row)
                 col: 1, // Starting from the second column
                 direction: Direction::North,
             }];
 
-            field.traverse(beams);
+            let energized = field.traverse(beams);
 
-            assert_eq!(field.nodes[3][1].energized, 0x1); // Energized by northward beam
-            assert_eq!(field.nodes[2][1].energized, 0x1);
-            assert_eq!(field.nodes[1][1].energized, 0x1);
-            assert_eq!(field.nodes[0][1].energized, 0x1);
+            assert_eq!(energized.get(3, 1), 0x1); // Energized by northward beam
+            assert_eq!(energized.get(2, 1), 0x1);
+            assert_eq!(energized.get(1, 1), 0x1);
+            assert_eq!(energized.get(0, 1), 0x1);
 
             // Ensure other nodes in the grid are not energized
             for i in 0..4 as usize {
-                assert_eq!(field.nodes[i][0].energized, 0);
-                assert_eq!(field.nodes[i][2].energized, 0);
-                assert_eq!(field.nodes[i][3].energized, 0);
+                assert_eq!(energized.get(i, 0), 0);
+                assert_eq!(energized.get(i, 2), 0);
+                assert_eq!(energized.get(i, 3), 0);
             }
         }
 
@@ -394,16 +424,16 @@ pub mod part1 {
                 ".|....-|.\\",
                 "..//.|....",
             ];
-            let mut field = Field::from_lines(&lines);
+            let field = Field::from_lines(&lines);
             let beams = vec![Beam {
                 row: 0,
                 col: 0,
                 direction: Direction::East,
             }];
 
-            field.traverse(beams);
+            let energized = field.traverse(beams);
 
-            assert_eq!(field.score(), 46);
+            assert_eq!(energized.count(), 46);
         }
     }
 }
@@ -413,63 +443,55 @@ pub mod part2 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
-            let field = Field::from_file(file_name);
-            let mut result: u64 = 0;
+        fn solve(input: &str) -> String {
+            let field = Field::from_text(input);
+            let mut result = 0;
 
             let col_count = field.column_count;
             let row_count = field.nodes.len();
 
             // from north to south
             for i in 0..col_count {
-                let mut current_field = field.clone();
                 let beams = vec![Beam {
                     row: 0,
                     col: i as i32,
                     direction: Direction::South,
                 }];
 
-                current_field.traverse(beams);
-                result = std::cmp::max(result, current_field.score());
+                result = std::cmp::max(result, field.traverse(beams).count());
             }
 
             // from south to north
             for i in 0..col_count {
-                let mut current_field = field.clone();
                 let beams = vec![Beam {
                     row: (row_count - 1) as i32,
                     col: i as i32,
                     direction: Direction::North,
                 }];
 
-                current_field.traverse(beams);
-                result = std::cmp::max(result, current_field.score());
+                result = std::cmp::max(result, field.traverse(beams).count());
             }
 
             // from west to east
             for i in 0..row_count {
-                let mut current_field = field.clone();
                 let beams = vec![Beam {
                     row: i as i32,
                     col: 0,
                     direction: Direction::East,
                 }];
 
-                current_field.traverse(beams);
-                result = std::cmp::max(result, current_field.score());
+                result = std::cmp::max(result, field.traverse(beams).count());
             }
 
             // from west to east
             for i in 0..row_count {
-                let mut current_field = field.clone();
                 let beams = vec![Beam {
                     row: i as i32,
                     col: (col_count - 1) as i32,
                     direction: Direction::East,
                 }];
 
-                current_field.traverse(beams);
-                result = std::cmp::max(result, current_field.score());
+                result = std::cmp::max(result, field.traverse(beams).count());
             }
 
             result.to_string()
@@ -1,5 +1,3 @@
-use std::collections::{BinaryHeap, HashMap};
-
 use aoc::Direction;
 
 type Point = aoc::Point<i32>;
@@ -8,27 +6,6 @@ fn manhattan_distance(one: &Point, another: &Point) -> i32 {
     (one.row - another.row).abs() + (one.col - another.col).abs()
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-struct Waypoint {
-    pos: Point,
-    direction: Direction,
-    heat_loss: i32,
-    heuristic: i32,
-    previous: Option<Point>,
-}
-
-impl Ord for Waypoint {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        (other.heat_loss + other.heuristic).cmp(&(self.heat_loss + &self.heuristic))
-    }
-}
-
-impl PartialOrd for Waypoint {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(&other))
-    }
-}
-
 #[derive(Debug, Default, Clone)]
 struct Field {
     nodes: Vec<Vec<i32>>,
@@ -36,40 +13,42 @@ struct Field {
     col_count: i32,
 }
 
-fn get_straight_path_length(direction: Direction, point: Point, waypoints: &Vec<Vec<Option<Waypoint>>>) -> i32 {
-    let mut result = 0;
-    let mut point = point;
-    loop {
-        let waypoint = waypoints[point.row as usize][point.col as usize];
-        match waypoint {
-            None => break,
-            Some(waypoint) => {
-                if waypoint.direction != direction {
-                    break;
-                }
-                result += 1;
-                match waypoint.previous {
-                    None => break,
-                    Some(new_point) => point = new_point,
-                }
-            }
-        }
-    }
-    result
+// Position of a crucible along with how it got there: the direction
+// of the last move and how many moves in a row were made in it
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+struct Crucible {
+    pos: Point,
+    direction: Direction,
+    straight: i32,
 }
 
-fn get_empty_waypoints(field: &Field) -> Vec<Vec<Option<Waypoint>>> {
-    let mut result: Vec<Vec<Option<Waypoint>>> = Vec::with_capacity(field.nodes.len());
-    for row in field.nodes.iter() {
-        result.push(vec![None; row.len()]);
+static DIRECTIONS: [Direction; 4] = [Direction::North, Direction::West, Direction::South, Direction::East];
+
+fn step(point: Point, direction: Direction) -> Point {
+    match direction {
+        Direction::North => Point {
+            row: point.row - 1,
+            col: point.col,
+        },
+        Direction::West => Point {
+            row: point.row,
+            col: point.col - 1,
+        },
+        Direction::South => Point {
+            row: point.row + 1,
+            col: point.col,
+        },
+        Direction::East => Point {
+            row: point.row,
+            col: point.col + 1,
+        },
+        Direction::Unknown => unreachable!(),
     }
-    result
 }
 
 impl Field {
-    fn from_file(file_name: &str) -> Field {
-        let file_content = std::fs::read_to_string(file_name).unwrap();
-        let lines = file_content.lines().collect::<Vec<_>>();
+    fn from_text(input: &str) -> Field {
+        let lines = input.lines().collect::<Vec<_>>();
         Field::from_lines(&lines)
     }
 
@@ -87,117 +66,86 @@ impl Field {
         result
     }
 
-    fn get_all_neighbors(&self, point: Point) -> HashMap<Direction, Point> {
-        let neighbors = HashMap::from([
-            (
-                Direction::North,
-                Point {
-                    row: point.row - 1,
-                    col: point.col,
-                },
-            ),
-            (
-                Direction::South,
-                Point {
-                    row: point.row + 1,
-                    col: point.col,
-                },
-            ),
-            (
-                Direction::West,
-                Point {
-                    row: point.row,
-                    col: point.col - 1,
-                },
-            ),
-            (
-                Direction::East,
-                Point {
-                    row: point.row,
-                    col: point.col + 1,
-                },
-            ),
-        ]);
-
-        let mut result = HashMap::new();
-
-        for (direction, point) in neighbors.into_iter() {
-            if point.col < 0 || point.row < 0 || point.col >= self.col_count || point.row >= self.row_count {
+    fn get_successors(&self, crucible: &Crucible, min_straight: i32, max_straight: i32) -> Vec<(Crucible, i32)> {
+        let mut result = Vec::with_capacity(3);
+        for direction in DIRECTIONS.iter().copied() {
+            if direction == crucible.direction.opposite() {
+                continue;
+            }
+            let straight = if direction == crucible.direction {
+                crucible.straight + 1
+            } else {
+                // the very first move can go anywhere
+                if crucible.direction != Direction::Unknown && crucible.straight < min_straight {
+                    continue;
+                }
+                1
+            };
+            if straight > max_straight {
+                continue;
+            }
+            let pos = step(crucible.pos, direction);
+            if pos.col < 0 || pos.row < 0 || pos.col >= self.col_count || pos.row >= self.row_count {
                 continue;
             }
-            result.insert(direction, point);
+            let heat_loss = self.nodes[pos.row as usize][pos.col as usize];
+            result.push((
+                Crucible {
+                    pos,
+                    direction,
+                    straight,
+                },
+                heat_loss,
+            ));
         }
-
         result
     }
 
-    fn traverse(&self, start: Point, finish: Point) -> Option<i32> {
-        let mut heap = BinaryHeap::new();
-
-        let mut visited = get_empty_waypoints(&self);
-
-        let start_waypoint = Waypoint {
+    fn traverse(&self, start: Point, finish: Point, min_straight: i32, max_straight: i32) -> Option<i32> {
+        let start = Crucible {
             pos: start,
             direction: Direction::Unknown,
-            heat_loss: 0,
-            heuristic: manhattan_distance(&start, &finish),
-            previous: None,
+            straight: 0,
         };
 
-        heap.push(start_waypoint);
-
-        while let Some(waypoint) = heap.pop() {
-            if waypoint.pos == finish {
-                return Some(waypoint.heat_loss);
-            }
-
-            let mut neighbors = self.get_all_neighbors(waypoint.pos);
-
-            // ignore backward path
-            neighbors.remove(&waypoint.direction.opposite());
-
-            for (direction, point) in neighbors.iter() {
-                let heat_loss = waypoint.heat_loss + self.nodes[point.row as usize][point.col as usize];
-
-                // ignore path if it's too long
-                if let Some(prev_point) = waypoint.previous {
-                    if get_straight_path_length(*direction, prev_point, &visited) > 2 {
-                        continue;
-                    }
-                }
-
-                let new_waypoint = Waypoint {
-                    pos: *point,
-                    direction: *direction,
-                    heat_loss: heat_loss,
-                    heuristic: manhattan_distance(&point, &finish),
-                    previous: Some(waypoint.pos),
-                };
-
-                // update already seen paths
-                if let Some(visited_waypoint) = visited[point.row as usize][point.col as usize] {
-                    if visited_waypoint.heat_loss + visited_waypoint.heuristic
-                        < new_waypoint.heat_loss + new_waypoint.heuristic
-                    {
-                        continue;
-                    }
-                }
-
-                heap.push(new_waypoint);
-            }
-            visited[waypoint.pos.row as usize][waypoint.pos.col as usize] = Some(waypoint);
-        }
-
-        None
+        let (_, heat_loss) = aoc::search::astar(
+            [start],
+            |crucible| {
+                aoc::trace!("expanding {:?}", crucible);
+                self.get_successors(crucible, min_straight, max_straight)
+            },
+            |crucible| manhattan_distance(&crucible.pos, &finish),
+            |crucible| crucible.pos == finish && crucible.straight >= min_straight,
+        )?;
+        Some(heat_loss)
     }
 }
 
+#[allow(dead_code)] // used in tests
+fn get_test_input() -> Vec<&'static str> {
+    vec![
+        "2413432311323",
+        "3215453535623",
+        "3255245654254",
+        "3446585845452",
+        "4546657867536",
+        "1438598798454",
+        "4457876987766",
+        "3637877979653",
+        "4654967986887",
+        "4564679986453",
+        "1224686865563",
+        "2546548887735",
+        "4322674655533",
+    ]
+}
+
 pub mod part1 {
     use crate::day_17::{Field, Point};
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
-            let field = Field::from_file(file_name);
+        fn solve(input: &str) -> String {
+            let field = Field::from_text(input);
 
             let start_point = Point { row: 0, col: 0 };
             let finish_point = Point {
@@ -205,7 +153,7 @@ pub mod part1 {
                 col: field.col_count - 1,
             };
 
-            let score = field.traverse(start_point, finish_point).expect("path not found");
+            let score = field.traverse(start_point, finish_point, 0, 3).expect("path not found");
             score.to_string()
         }
 
@@ -224,57 +172,7 @@ pub mod part1 {
 
     #[cfg(test)]
     mod tests {
-        use crate::day_17::{get_empty_waypoints, get_straight_path_length, Direction, Field, Point, Waypoint};
-
-        #[test]
-        fn cmp_test() {
-            let w1 = Waypoint {
-                pos: Point { row: 0, col: 0 },
-                direction: Direction::Unknown,
-                heat_loss: 1,
-                heuristic: 5,
-                previous: None,
-            };
-
-            let w2 = Waypoint {
-                pos: Point { row: 0, col: 0 },
-                direction: Direction::Unknown,
-                heat_loss: 12,
-                heuristic: 5,
-                previous: None,
-            };
-
-            assert!(w1 > w2);
-            assert!(w2 < w1);
-            assert!(w1 == w1);
-            assert!(w2 == w2);
-
-            let w3 = Waypoint {
-                pos: Point { row: 0, col: 0 },
-                direction: Direction::Unknown,
-                heat_loss: 1,
-                heuristic: 150,
-                previous: None,
-            };
-
-            assert!(w1 > w3);
-            assert!(w3 < w1);
-            assert!(w3 == w3);
-        }
-
-        #[test]
-        fn test_get_empty_waypoints() {
-            let lines = vec!["123", "456", "019"];
-            let field = Field::from_lines(&lines);
-            let waypoints = get_empty_waypoints(&field);
-
-            for (f_row, w_row) in std::iter::zip(field.nodes.iter(), waypoints.iter()) {
-                assert_eq!(f_row.len(), w_row.len());
-                for w in w_row {
-                    assert!(w.is_none());
-                }
-            }
-        }
+        use crate::day_17::{get_test_input, Field, Point};
 
         #[test]
         fn from_lines_test() {
@@ -294,28 +192,9 @@ pub mod part1 {
             assert_eq!(field.nodes[2][2], 9);
         }
 
-        #[test]
-        fn get_straight_path_length_test() {}
-
         #[test]
         fn traverse_test() {
-            let lines = vec![
-                "2413432311323",
-                "3215453535623",
-                "3255245654254",
-                "3446585845452",
-                "4546657867536",
-                "1438598798454",
-                "4457876987766",
-                "3637877979653",
-                "4654967986887",
-                "4564679986453",
-                "1224686865563",
-                "2546548887735",
-                "4322674655533",
-            ];
-
-            let field = Field::from_lines(&lines);
+            let field = Field::from_lines(&get_test_input());
 
             assert_eq!(field.col_count, 13);
             assert_eq!(field.row_count, 13);
@@ -323,8 +202,75 @@ pub mod part1 {
             let start_point = Point { row: 0, col: 0 };
             let finish_point = Point { row: 12, col: 12 };
 
-            let result = field.traverse(start_point, finish_point);
+            let result = field.traverse(start_point, finish_point, 0, 3);
             assert_eq!(result, Some(102));
         }
     }
 }
+
+pub mod part2 {
+    use crate::day_17::{Field, Point};
+    pub struct Puzzle {}
+    impl aoc::Puzzle for Puzzle {
+        fn solve(input: &str) -> String {
+            let field = Field::from_text(input);
+
+            let start_point = Point { row: 0, col: 0 };
+            let finish_point = Point {
+                row: field.row_count - 1,
+                col: field.col_count - 1,
+            };
+
+            let score = field
+                .traverse(start_point, finish_point, 4, 10)
+                .expect("path not found");
+            score.to_string()
+        }
+
+        fn day() -> i32 {
+            17
+        }
+
+        fn part() -> i32 {
+            2
+        }
+
+        fn year() -> i32 {
+            2023
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::day_17::{get_test_input, Field, Point};
+
+        #[test]
+        fn traverse_test() {
+            let field = Field::from_lines(&get_test_input());
+
+            let start_point = Point { row: 0, col: 0 };
+            let finish_point = Point { row: 12, col: 12 };
+
+            let result = field.traverse(start_point, finish_point, 4, 10);
+            assert_eq!(result, Some(94));
+        }
+
+        #[test]
+        fn traverse_unfortunate_test() {
+            let lines = vec![
+                "111111111111",
+                "999999999991",
+                "999999999991",
+                "999999999991",
+                "999999999991",
+            ];
+            let field = Field::from_lines(&lines);
+
+            let start_point = Point { row: 0, col: 0 };
+            let finish_point = Point { row: 4, col: 11 };
+
+            let result = field.traverse(start_point, finish_point, 4, 10);
+            assert_eq!(result, Some(71));
+        }
+    }
+}
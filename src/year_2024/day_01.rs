@@ -33,12 +33,12 @@ pub mod part1 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(input_file_name: &str) -> String {
-            let mut input = Input::default();
-            for line in std::fs::read_to_string(input_file_name).unwrap().lines() {
-                input.add_line(line);
+        fn solve(input: &str) -> String {
+            let mut lists = Input::default();
+            for line in input.lines() {
+                lists.add_line(line);
             }
-            solve(&mut input).to_string()
+            solve(&mut lists).to_string()
         }
 
         fn day() -> i32 {
@@ -98,12 +98,12 @@ pub mod part2 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(input_file_name: &str) -> String {
-            let mut input = Input::default();
-            for line in std::fs::read_to_string(input_file_name).unwrap().lines() {
-                input.add_line(line);
+        fn solve(input: &str) -> String {
+            let mut lists = Input::default();
+            for line in input.lines() {
+                lists.add_line(line);
             }
-            solve(&mut input).to_string()
+            solve(&mut lists).to_string()
         }
 
         fn day() -> i32 {
@@ -76,9 +76,9 @@ pub mod part1 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(input_file_name: &str) -> String {
+        fn solve(input: &str) -> String {
             let mut result = 0;
-            for line in std::fs::read_to_string(input_file_name).unwrap().lines() {
+            for line in input.lines() {
                 let report = Report::from_string(line);
                 if is_report_safe(&report.levels) {
                     result += 1;
@@ -141,9 +141,9 @@ pub mod part2 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(input_file_name: &str) -> String {
+        fn solve(input: &str) -> String {
             let mut result = 0;
-            for line in std::fs::read_to_string(input_file_name).unwrap().lines() {
+            for line in input.lines() {
                 let report = Report::from_string(line);
                 if is_report_safe_dampened(&report.levels) {
                     result += 1;
@@ -1,35 +1,58 @@
-#[derive(Debug, PartialEq, Eq)]
+use aoc::vm::{Flow, Instruction, InstructionSet, Machine, Operand, Registers};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Command {
     Mul(i64, i64),
     Do,
     DoNot,
 }
 
-fn execute(commands: &[Command]) -> i64 {
-    let mut active = true;
-    let mut result = 0;
-    for c in commands.iter() {
-        match *c {
+const ACC: usize = 0;
+const ENABLED: usize = 1;
+
+impl Instruction for Command {
+    fn execute(&self, registers: &mut Registers) -> Flow {
+        match *self {
             Command::Mul(a, b) => {
-                if active {
-                    result += a * b
+                if registers[ENABLED] != 0 {
+                    registers[ACC] += a * b;
                 }
             }
-            Command::Do => active = true,
-            Command::DoNot => active = false,
+            Command::Do => registers[ENABLED] = 1,
+            Command::DoNot => registers[ENABLED] = 0,
         }
+        Flow::Next
     }
-    result
 }
 
-fn get_mul_commands(line: &str) -> Vec<Command> {
-    let re = regex::Regex::new(r"mul\(([0-9]{1,3}),([0-9]{1,3})\)").unwrap();
-    let captures = re.captures_iter(line);
-    let mut result = Vec::new();
-    for cap in captures {
-        result.push(Command::Mul(aoc::parse_or_panic(&cap[1]), aoc::parse_or_panic(&cap[2])));
+fn mul(args: &[Operand]) -> Option<Command> {
+    match args {
+        [Operand::Value(a), Operand::Value(b)] => Some(Command::Mul(*a, *b)),
+        _ => None,
     }
-    result
+}
+
+// Arguments are 1-3 digit numbers
+fn mul_instructions() -> InstructionSet<Command> {
+    InstructionSet::new(&["acc", "enabled"]).add("mul({3},{3})", mul)
+}
+
+fn all_instructions() -> InstructionSet<Command> {
+    mul_instructions()
+        .add("do()", |_| Some(Command::Do))
+        .add("don't()", |_| Some(Command::DoNot))
+}
+
+fn execute(commands: &[Command]) -> i64 {
+    let mut registers = all_instructions().registers();
+    registers[ENABLED] = 1;
+    let mut machine = Machine::new(commands.to_vec(), registers);
+    machine.run();
+    machine.registers[ACC]
+}
+
+fn get_mul_commands(line: &str) -> Vec<Command> {
+    mul_instructions().find_all(line)
 }
 
 #[cfg(test)]
@@ -53,6 +76,11 @@ mod tests {
         let line = "xmul(2,4567)";
         let result = super::get_mul_commands(line);
         assert!(result.is_empty());
+
+        // signs, leading zeros beyond three digits and spaces are corrupted
+        let line = "mul(+5,3)mul(-5,3)mul(0012,4)mul( 2,4)mul(012,4)";
+        let result = super::get_mul_commands(line);
+        assert_eq!(result, vec![Command::Mul(12, 4)]);
     }
 
     #[test]
@@ -72,23 +100,16 @@ mod tests {
 }
 
 pub mod part1 {
-    use super::{get_mul_commands, Command};
+    use super::{execute, get_mul_commands};
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(input_file_name: &str) -> String {
-            let mut result = 0;
-            for line in std::fs::read_to_string(input_file_name).unwrap().lines() {
-                let commands = get_mul_commands(line);
-                for command in commands {
-                    match command {
-                        Command::Mul(a, b) => result += a * b,
-                        Command::Do => unreachable!(),
-                        Command::DoNot => unreachable!(),
-                    };
-                }
+        fn solve(input: &str) -> String {
+            let mut commands = Vec::new();
+            for line in input.lines() {
+                commands.extend(get_mul_commands(line));
             }
-            result.to_string()
+            execute(&commands).to_string()
         }
 
         fn day() -> i32 {
@@ -106,67 +127,18 @@ pub mod part1 {
 }
 
 pub mod part2 {
-    use super::{execute, Command};
-    use std::num::ParseIntError;
-
-    #[derive(Debug)]
-    #[allow(dead_code)]
-    enum ParseError {
-        InvalidFormat,
-        ParseError(ParseIntError),
-    }
-
-    impl From<ParseIntError> for ParseError {
-        fn from(err: ParseIntError) -> Self {
-            ParseError::ParseError(err)
-        }
-    }
-
-    fn parse_mul_arguments(line: &str) -> Result<(i64, i64), ParseError> {
-        let parts = line.split(',').collect::<Vec<_>>();
-        if parts.len() != 2 {
-            return Err(ParseError::InvalidFormat);
-        }
-        Ok((parts[0].parse::<i64>()?, parts[1].parse::<i64>()?))
-    }
+    use super::{all_instructions, execute, Command};
 
     fn parse_commands(line: &str) -> Vec<Command> {
-        let mut line = line;
-        let mut result = Vec::new();
-        while !line.is_empty() {
-            if line.starts_with("do()") {
-                result.push(Command::Do);
-                line = &line[4..];
-            } else if line.starts_with("don't()") {
-                result.push(Command::DoNot);
-                line = &line[6..];
-            } else {
-                if line.starts_with("mul(") {
-                    if let Some(closing_brace_pos) = line.chars().position(|c| c == ')') {
-                        let command_args_str = &line[4..closing_brace_pos];
-                        if let Ok(args) = parse_mul_arguments(command_args_str) {
-                            result.push(Command::Mul(args.0, args.1));
-                            line = &line[closing_brace_pos..];
-                        }
-                        line = &line[1..];
-                    } else {
-                        // closing brace not found, can exit early
-                        break;
-                    }
-                } else {
-                    line = &line[1..];
-                }
-            }
-        }
-        result
+        all_instructions().find_all(line)
     }
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(input_file_name: &str) -> String {
+        fn solve(input: &str) -> String {
             let mut commands = Vec::new();
-            for line in std::fs::read_to_string(input_file_name).unwrap().lines() {
-                let line_commands = parse_commands(&line);
+            for line in input.lines() {
+                let line_commands = parse_commands(line);
                 commands.extend(line_commands);
             }
             execute(&commands).to_string()
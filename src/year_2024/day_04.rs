@@ -52,12 +52,8 @@ pub mod part1 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(input_file_name: &str) -> String {
-            let lines: Vec<String> = std::fs::read_to_string(input_file_name)
-                .unwrap()
-                .lines()
-                .map(String::from)
-                .collect();
+        fn solve(input: &str) -> String {
+            let lines: Vec<String> = input.lines().map(String::from).collect();
             count_xmas(&lines).to_string()
         }
 
@@ -149,12 +145,8 @@ pub mod part2 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(input_file_name: &str) -> String {
-            let lines: Vec<String> = std::fs::read_to_string(input_file_name)
-                .unwrap()
-                .lines()
-                .map(String::from)
-                .collect();
+        fn solve(input: &str) -> String {
+            let lines: Vec<String> = input.lines().map(String::from).collect();
             count_x_mas(&lines).to_string()
         }
 
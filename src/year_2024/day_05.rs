@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use aoc::graph::PartialOrder;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 struct ValidationRule {
@@ -6,51 +6,34 @@ struct ValidationRule {
     after: i32,
 }
 
-fn is_rule_satisfied(ordering: &HashMap<i32, i32>, rule: &ValidationRule) -> bool {
-    let maybe_before = ordering.get(&rule.before);
-    let maybe_after = ordering.get(&rule.after);
-    if maybe_before.is_none() || maybe_after.is_none() {
-        return true;
-    }
-    maybe_before.unwrap() < maybe_after.unwrap()
-}
+type PageOrder = PartialOrder<i32>;
 
-fn is_valid(pages: &Vec<i32>, rules: &Vec<ValidationRule>) -> bool {
-    // prepare page ordering
-    let mut ordering: HashMap<i32, i32> = HashMap::with_capacity(pages.len());
-    for (idx, page_number) in pages.iter().enumerate() {
-        ordering.insert(*page_number, idx as i32);
-    }
-
-    // check if all rules are satisfied
-    for rule in rules.iter() {
-        if !is_rule_satisfied(&ordering, rule) {
-            return false;
-        }
-    }
+fn get_page_order(rules: &[ValidationRule]) -> PageOrder {
+    PartialOrder::new(rules.iter().map(|rule| (rule.before, rule.after)))
+}
 
-    true
+fn is_valid(pages: &[i32], order: &PageOrder) -> bool {
+    order.is_sorted(pages)
 }
 
-fn parse_input(input_file_name: &str) -> (Vec<ValidationRule>, Vec<Vec<i32>>) {
-    let mut rules = Vec::new();
-    let mut pages = Vec::new();
-    let mut reading_rules = true;
-    for line in std::fs::read_to_string(input_file_name).unwrap().lines() {
-        if reading_rules {
-            if line.is_empty() {
-                reading_rules = false;
-            } else {
-                let parts: Vec<&str> = line.split('|').collect();
-                rules.push(ValidationRule {
-                    before: aoc::parse_or_panic(parts[0]),
-                    after: aoc::parse_or_panic(parts[1]),
-                });
-            }
-        } else {
-            pages.push(line.split(',').map(|s| aoc::parse_or_panic::<i32>(s)).collect());
-        }
-    }
+fn parse_input(input: &str) -> (Vec<ValidationRule>, Vec<Vec<i32>>) {
+    let mut paragraphs = aoc::input::paragraphs(input);
+
+    let rules = paragraphs
+        .next()
+        .unwrap_or_default()
+        .lines()
+        .map(|line| {
+            let (before, after) = aoc::parse::pair(line, '|').unwrap_or_else(|e| panic!("{}", e));
+            ValidationRule { before, after }
+        })
+        .collect();
+    let pages = paragraphs
+        .next()
+        .unwrap_or_default()
+        .lines()
+        .map(|line| aoc::input::ints(line).collect())
+        .collect();
 
     (rules, pages)
 }
@@ -83,12 +66,13 @@ fn get_test_rules() -> Vec<ValidationRule> {
 }
 
 pub mod part1 {
-    use super::{is_valid, parse_input, ValidationRule};
+    use super::{get_page_order, is_valid, parse_input, ValidationRule};
 
-    fn solve(pages: &Vec<Vec<i32>>, rules: &Vec<ValidationRule>) -> i32 {
+    fn solve(pages: &Vec<Vec<i32>>, rules: &[ValidationRule]) -> i32 {
         let mut result = 0;
+        let order = get_page_order(rules);
         for page_set in pages {
-            if is_valid(&page_set, &rules) {
+            if is_valid(page_set, &order) {
                 result += page_set[page_set.len() / 2];
             }
         }
@@ -97,8 +81,8 @@ pub mod part1 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(input_file_name: &str) -> String {
-            let (rules, pages) = parse_input(input_file_name);
+        fn solve(input: &str) -> String {
+            let (rules, pages) = parse_input(input);
             solve(&pages, &rules).to_string()
         }
 
@@ -117,11 +101,12 @@ pub mod part1 {
 
     #[cfg(test)]
     mod tests {
-        use crate::day_05::{get_test_rules, is_valid, part1::solve};
+        use crate::day_05::{get_page_order, get_test_rules, is_valid, part1::solve};
 
         #[test]
         fn is_valid_test() {
             let rules = get_test_rules();
+            let order = get_page_order(&rules);
 
             let pages = vec![
                 vec![75, 47, 61, 53, 29],
@@ -132,12 +117,12 @@ pub mod part1 {
                 vec![97, 13, 75, 29, 47],
             ];
 
-            assert_eq!(is_valid(&pages[0], &rules), true);
-            assert_eq!(is_valid(&pages[1], &rules), true);
-            assert_eq!(is_valid(&pages[2], &rules), true);
-            assert_eq!(is_valid(&pages[3], &rules), false);
-            assert_eq!(is_valid(&pages[4], &rules), false);
-            assert_eq!(is_valid(&pages[5], &rules), false);
+            assert_eq!(is_valid(&pages[0], &order), true);
+            assert_eq!(is_valid(&pages[1], &order), true);
+            assert_eq!(is_valid(&pages[2], &order), true);
+            assert_eq!(is_valid(&pages[3], &order), false);
+            assert_eq!(is_valid(&pages[4], &order), false);
+            assert_eq!(is_valid(&pages[5], &order), false);
 
             assert_eq!(solve(&pages, &rules), 143);
         }
@@ -145,46 +130,20 @@ pub mod part1 {
 }
 
 pub mod part2 {
-    use std::{cmp::Ordering, collections::HashSet};
+    use super::{get_page_order, is_valid, parse_input, PageOrder, ValidationRule};
 
-    use super::{is_valid, parse_input, ValidationRule};
-
-    fn fix_ordering(pages: &Vec<i32>, rules: &HashSet<ValidationRule>) -> Vec<i32> {
-        let mut result = pages.clone();
-
-        result.sort_by(|left, right| {
-            if let Some(_) = rules.get(&ValidationRule {
-                before: *left,
-                after: *right,
-            }) {
-                return Ordering::Less;
-            }
-            if let Some(_) = rules.get(&ValidationRule {
-                before: *right,
-                after: *left,
-            }) {
-                return Ordering::Greater;
-            }
-            return Ordering::Equal;
-        });
-
-        result
-    }
-
-    fn get_rules_set(rules: &Vec<ValidationRule>) -> HashSet<ValidationRule> {
-        let mut result: HashSet<ValidationRule> = HashSet::with_capacity(rules.len());
-        for rule in rules.iter() {
-            result.insert(*rule);
-        }
-        result
+    fn fix_ordering(pages: &[i32], order: &PageOrder) -> Vec<i32> {
+        order
+            .sort(pages)
+            .unwrap_or_else(|e| panic!("Unable to order {:?}: {}", pages, e))
     }
 
-    fn solve(pages: &Vec<Vec<i32>>, rules: &Vec<ValidationRule>) -> i32 {
+    fn solve(pages: &Vec<Vec<i32>>, rules: &[ValidationRule]) -> i32 {
         let mut result = 0;
-        let rules_set = get_rules_set(rules);
+        let order = get_page_order(rules);
         for page_set in pages {
-            if !is_valid(&page_set, &rules) {
-                let fixed = fix_ordering(&page_set, &rules_set);
+            if !is_valid(page_set, &order) {
+                let fixed = fix_ordering(page_set, &order);
                 result += fixed[fixed.len() / 2];
             }
         }
@@ -193,8 +152,8 @@ pub mod part2 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(input_file_name: &str) -> String {
-            let (rules, pages) = parse_input(input_file_name);
+        fn solve(input: &str) -> String {
+            let (rules, pages) = parse_input(input);
             solve(&pages, &rules).to_string()
         }
 
@@ -214,22 +173,22 @@ pub mod part2 {
     #[cfg(test)]
     mod tests {
         use crate::day_05::{
-            get_test_rules,
-            part2::{fix_ordering, get_rules_set, solve},
+            get_page_order, get_test_rules,
+            part2::{fix_ordering, solve},
         };
 
         #[test]
         fn test_fix_ordering() {
-            let rules = get_rules_set(&get_test_rules());
+            let order = get_page_order(&get_test_rules());
             assert_eq!(
-                fix_ordering(&vec![75, 97, 47, 61, 53], &rules),
+                fix_ordering(&vec![75, 97, 47, 61, 53], &order),
                 vec![97, 75, 47, 61, 53]
             );
 
-            assert_eq!(fix_ordering(&vec![61, 13, 29], &rules), vec![61, 29, 13]);
+            assert_eq!(fix_ordering(&vec![61, 13, 29], &order), vec![61, 29, 13]);
 
             assert_eq!(
-                fix_ordering(&vec![97, 13, 75, 29, 47], &rules),
+                fix_ordering(&vec![97, 13, 75, 29, 47], &order),
                 vec![97, 75, 47, 29, 13]
             );
         }
@@ -1,21 +1,22 @@
-use aoc::Direction;
+use aoc::viz::{Cell, Color, Frame};
+use aoc::{Direction, DirectionGrid};
 type Point = aoc::Point<i32>;
 
 #[derive(Default, Debug, PartialEq, Eq, Hash, Clone, Copy)]
-struct Guard {
+pub struct Guard {
     pos: Point,
     direction: Direction,
 }
 
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
-enum Node {
+pub enum Node {
     #[default]
     Empty,
     Obstacle,
 }
 
 #[derive(Default, Clone)]
-struct Field<N> {
+pub struct Field<N> {
     nodes: Vec<Vec<N>>,
 }
 
@@ -59,17 +60,22 @@ enum TraverseResult {
     Stuck,
 }
 
-fn traverse(field: &Field<Node>, guard: &Guard) -> (Field<i32>, TraverseResult) {
-    let mut guard = *guard;
-    let mut path = Field::<i32>::default();
+fn traverse(field: &Field<Node>, guard: &Guard) -> (DirectionGrid, TraverseResult) {
+    traverse_with(field, guard, |_, _| {})
+}
 
-    // initialize path matrix
-    for _ in 0..field.nodes.len() {
-        path.nodes.push(vec![0; field.nodes[0].len()]);
-    }
+// Same as traverse(), on_step is called with the path so far and the guard
+// before every step
+fn traverse_with<F>(field: &Field<Node>, guard: &Guard, mut on_step: F) -> (DirectionGrid, TraverseResult)
+where
+    F: FnMut(&DirectionGrid, &Guard),
+{
+    let mut guard = *guard;
+    let mut path = DirectionGrid::with_size(field.nodes.len(), field.nodes[0].len());
 
     loop {
-        path.nodes[guard.pos.row as usize][guard.pos.col as usize] |= guard.direction as i32;
+        path.set(guard.pos.row as usize, guard.pos.col as usize, guard.direction);
+        on_step(&path, &guard);
 
         let mut new_guard_pos = guard.pos;
         match guard.direction {
@@ -100,25 +106,37 @@ fn traverse(field: &Field<Node>, guard: &Guard) -> (Field<i32>, TraverseResult)
             guard.pos = new_guard_pos;
         }
 
-        let path_node = &path.nodes[guard.pos.row as usize][guard.pos.col as usize];
-        if *path_node & (guard.direction as i32) != 0 {
+        if path.test(guard.pos.row as usize, guard.pos.col as usize, guard.direction) {
             return (path, TraverseResult::Stuck);
         }
     }
 }
 
-fn count_visited_nodes(path: &Field<i32>) -> i32 {
-    let mut result = 0;
-    for row in path.nodes.iter() {
-        for node in row.iter() {
-            if *node != 0 {
-                result += 1;
-            }
+// Obstacles, the visited nodes and the guard on top of them
+fn frame(field: &Field<Node>, path: &DirectionGrid, guard: &Guard) -> Frame {
+    let mut result = Frame::with_size(field.nodes.len(), field.nodes[0].len(), Cell::new('.'));
+    for (row, nodes) in field.nodes.iter().enumerate() {
+        for (col, node) in nodes.iter().enumerate() {
+            let cell = match node {
+                Node::Obstacle => Cell::new('#'),
+                Node::Empty if path.get(row, col) != 0 => Cell::new('X').fg(Color::Yellow),
+                Node::Empty => continue,
+            };
+            let point = Point {
+                row: row as i32,
+                col: col as i32,
+            };
+            result.set(&point, cell);
         }
     }
+    result.set(&guard.pos, Cell::new('@').fg(Color::Red));
     result
 }
 
+fn count_visited_nodes(path: &DirectionGrid) -> usize {
+    path.count()
+}
+
 #[allow(dead_code)] // used in tests
 fn get_test_filed() -> Vec<String> {
     vec![
@@ -137,7 +155,11 @@ fn get_test_filed() -> Vec<String> {
 
 #[cfg(test)]
 mod tests {
-    use super::{count_visited_nodes, get_test_filed, parse_field, traverse, Direction, Node, Point, TraverseResult};
+    use aoc::Day;
+
+    use super::{
+        count_visited_nodes, get_test_filed, parse_field, traverse, Direction, Node, Point, Solution, TraverseResult,
+    };
 
     #[test]
     fn test_parse_field() {
@@ -182,89 +204,98 @@ mod tests {
             assert_eq!(result, TraverseResult::Exited);
         }
     }
+
+    #[test]
+    fn test_solution() {
+        let parsed = Solution::parse(&get_test_filed().join("\n"));
+        assert_eq!(Solution::part1(&parsed), "41");
+        assert_eq!(Solution::part2(&parsed), "6");
+    }
 }
 
 pub mod part1 {
-    use crate::day_06::TraverseResult;
-
-    use super::{count_visited_nodes, parse_field, traverse};
+    use super::{count_visited_nodes, frame, traverse_with, Field, Guard, Node, Solution, TraverseResult};
 
-    pub struct Puzzle {}
-    impl aoc::Puzzle for Puzzle {
-        fn solve(input_file_name: &str) -> String {
-            let (field, guard) = parse_field(std::fs::read_to_string(input_file_name).unwrap().lines());
-            let (path, traverse_result) = traverse(&field, &guard);
-            assert_eq!(traverse_result, TraverseResult::Exited);
-            count_visited_nodes(&path).to_string()
-        }
-
-        fn day() -> i32 {
-            6
-        }
+    #[allow(dead_code)] // used in tests, the runner goes through solve_day
+    pub type Puzzle = aoc::DayPart<Solution, 1>;
 
-        fn part() -> i32 {
-            1
-        }
-
-        fn year() -> i32 {
-            2024
-        }
+    pub(super) fn solve(field: &Field<Node>, guard: &Guard) -> usize {
+        let visualize = aoc::viz::is_enabled();
+        let (path, traverse_result) = traverse_with(field, guard, |path, guard| {
+            if visualize {
+                aoc::viz::show(&frame(field, path, guard));
+            }
+        });
+        assert_eq!(traverse_result, TraverseResult::Exited);
+        count_visited_nodes(&path)
     }
 }
 
 pub mod part2 {
-    use crate::day_06::{parse_field, traverse};
+    use super::{traverse, Field, Guard, Node, Solution, TraverseResult};
 
-    use super::{Field, Guard, Node, TraverseResult};
+    #[allow(dead_code)] // used in tests, the runner goes through solve_day
+    pub type Puzzle = aoc::DayPart<Solution, 2>;
 
-    fn count_obstacles(field: &Field<Node>, guard: &Guard) -> i32 {
-        let (path, _) = traverse(&field, &guard);
+    pub(super) fn count_obstacles(field: &Field<Node>, guard: &Guard) -> i32 {
+        let (path, _) = traverse(field, guard);
+        let mut altered_field = field.clone();
         let mut result = 0;
-        for (row_idx, row) in path.nodes.iter().enumerate() {
-            for (col_idx, node) in row.iter().enumerate() {
-                if *node == 0 || (guard.pos.row == row_idx as i32 && guard.pos.col == col_idx as i32) {
-                    continue;
-                }
-                let mut altered_field = field.clone();
-                altered_field.nodes[row_idx][col_idx] = Node::Obstacle;
-                let (_, traverse_result) = traverse(&altered_field, &guard);
-                if traverse_result == TraverseResult::Stuck {
-                    result += 1;
-                }
+        // an obstacle only matters if it's placed on the original path
+        for pos in path.to_bit_grid().iter() {
+            if pos == guard.pos {
+                continue;
             }
+            let (row, col) = (pos.row as usize, pos.col as usize);
+            altered_field.nodes[row][col] = Node::Obstacle;
+            let (_, traverse_result) = traverse(&altered_field, guard);
+            if traverse_result == TraverseResult::Stuck {
+                result += 1;
+            }
+            altered_field.nodes[row][col] = Node::Empty;
         }
 
         result
     }
 
-    pub struct Puzzle {}
-    impl aoc::Puzzle for Puzzle {
-        fn solve(input_file_name: &str) -> String {
-            let (field, guard) = parse_field(std::fs::read_to_string(input_file_name).unwrap().lines());
-            count_obstacles(&field, &guard).to_string()
-        }
-
-        fn day() -> i32 {
-            6
-        }
-
-        fn part() -> i32 {
-            2
-        }
-
-        fn year() -> i32 {
-            2024
-        }
-    }
-
     #[cfg(test)]
     mod tests {
-        use crate::day_06::{get_test_filed, parse_field, part2::count_obstacles};
+        use crate::day_06::{get_test_filed, parse_field, part2::count_obstacles, part2::Puzzle};
 
         #[test]
         fn test_solve() {
             let (field, guard) = parse_field(get_test_filed().iter());
             assert_eq!(count_obstacles(&field, &guard), 6);
         }
+
+        #[test]
+        fn test_puzzle() {
+            assert_eq!(<Puzzle as aoc::Puzzle>::solve(&get_test_filed().join("\n")), "6");
+        }
+    }
+}
+
+pub struct Solution {}
+impl aoc::Day for Solution {
+    type Parsed = (Field<Node>, Guard);
+
+    fn parse(input: &str) -> Self::Parsed {
+        parse_field(input.lines())
+    }
+
+    fn part1((field, guard): &Self::Parsed) -> String {
+        part1::solve(field, guard).to_string()
+    }
+
+    fn part2((field, guard): &Self::Parsed) -> String {
+        part2::count_obstacles(field, guard).to_string()
+    }
+
+    fn day() -> i32 {
+        6
+    }
+
+    fn year() -> i32 {
+        2024
     }
 }
@@ -1,15 +1,11 @@
+use aoc::operators::Operator;
+
 #[derive(Debug, PartialEq, Eq)]
 struct Equation {
     value: u64,
     operands: Vec<u64>,
 }
 
-enum Operation {
-    Add,
-    Mul,
-    Concat,
-}
-
 fn parse_equation(line: &str) -> Equation {
     let parts = line.split(':').collect::<Vec<&str>>();
     assert_eq!(parts.len(), 2);
@@ -20,6 +16,18 @@ fn parse_equation(line: &str) -> Equation {
     }
 }
 
+fn is_valid(equation: &Equation, operators: &[Operator]) -> bool {
+    let found = aoc::operators::find_operators(&equation.operands, equation.value, operators);
+    if let Some(found) = found.as_ref() {
+        aoc::trace!(
+            "{} = {}",
+            equation.value,
+            aoc::operators::expression(&equation.operands, found)
+        );
+    }
+    found.is_some()
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -94,45 +102,19 @@ mod tests {
 }
 
 pub mod part1 {
-    use super::{parse_equation, Equation, Operation};
+    use aoc::operators::{ADD, MUL};
 
-    fn is_valid(value: u64, operands: &[u64], operation: Operation, target: u64) -> bool {
-        if value > target {
-            return false;
-        }
-        let new_value = match operation {
-            Operation::Add => value + operands[0],
-            Operation::Mul => value * operands[0],
-            _ => unreachable!(),
-        };
-
-        if operands.len() == 1 {
-            return new_value == target;
-        }
-
-        is_valid(new_value, &operands[1..], Operation::Add, target)
-            || is_valid(new_value, &operands[1..], Operation::Mul, target)
-    }
+    use super::{is_valid, parse_equation, Equation};
 
     fn is_valid_equation(equation: &Equation) -> bool {
-        is_valid(
-            equation.operands[0],
-            &equation.operands[1..],
-            Operation::Add,
-            equation.value,
-        ) || is_valid(
-            equation.operands[0],
-            &equation.operands[1..],
-            Operation::Mul,
-            equation.value,
-        )
+        is_valid(equation, &[ADD, MUL])
     }
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(input_file_name: &str) -> String {
+        fn solve(input: &str) -> String {
             let mut result = 0;
-            for line in std::fs::read_to_string(input_file_name).unwrap().lines() {
+            for line in input.lines() {
                 let eq = parse_equation(line);
                 if is_valid_equation(&eq) {
                     result += eq.value;
@@ -146,7 +128,7 @@ pub mod part1 {
         }
 
         fn part() -> i32 {
-            2
+            1
         }
 
         fn year() -> i32 {
@@ -218,63 +200,19 @@ pub mod part1 {
 }
 
 pub mod part2 {
-    use super::{parse_equation, Equation, Operation};
-
-    fn concat(a: u64, b: u64) -> u64 {
-        let mut b_copy = b;
-        let mut digits = 0;
-
-        while b_copy > 0 {
-            digits += 1;
-            b_copy /= 10;
-        }
-
-        a * 10_u64.pow(digits as u32) + b
-    }
-
-    fn is_valid(value: u64, operands: &[u64], operation: Operation, target: u64) -> bool {
-        if value > target {
-            return false;
-        }
-        let new_value = match operation {
-            Operation::Add => value + operands[0],
-            Operation::Mul => value * operands[0],
-            Operation::Concat => concat(value, operands[0]),
-        };
-
-        if operands.len() == 1 {
-            return new_value == target;
-        }
+    use aoc::operators::{ADD, CONCAT, MUL};
 
-        is_valid(new_value, &operands[1..], Operation::Add, target)
-            || is_valid(new_value, &operands[1..], Operation::Mul, target)
-            || is_valid(new_value, &operands[1..], Operation::Concat, target)
-    }
+    use super::{is_valid, parse_equation, Equation};
 
     fn is_valid_equation(equation: &Equation) -> bool {
-        is_valid(
-            equation.operands[0],
-            &equation.operands[1..],
-            Operation::Add,
-            equation.value,
-        ) || is_valid(
-            equation.operands[0],
-            &equation.operands[1..],
-            Operation::Mul,
-            equation.value,
-        ) || is_valid(
-            equation.operands[0],
-            &equation.operands[1..],
-            Operation::Concat,
-            equation.value,
-        )
+        is_valid(equation, &[ADD, MUL, CONCAT])
     }
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(input_file_name: &str) -> String {
+        fn solve(input: &str) -> String {
             let mut result = 0;
-            for line in std::fs::read_to_string(input_file_name).unwrap().lines() {
+            for line in input.lines() {
                 let eq = parse_equation(line);
                 if is_valid_equation(&eq) {
                     result += eq.value;
@@ -298,10 +236,9 @@ pub mod part2 {
 
     #[cfg(test)]
     mod tests {
-        use crate::day_07::{
-            part2::{concat, is_valid_equation},
-            Equation,
-        };
+        use aoc::math::concat;
+
+        use crate::day_07::{part2::is_valid_equation, Equation};
 
         #[test]
         fn test_concat() {
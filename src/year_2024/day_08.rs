@@ -280,8 +280,8 @@ pub mod part1 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(input_file_name: &str) -> String {
-            let (size, points) = parse_input(std::fs::read_to_string(input_file_name).unwrap().lines());
+        fn solve(input: &str) -> String {
+            let (size, points) = parse_input(input.lines());
             let antinodes = get_antinodes(&points, &size);
             antinodes.len().to_string()
         }
@@ -305,8 +305,8 @@ pub mod part2 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(input_file_name: &str) -> String {
-            let (size, points) = parse_input(std::fs::read_to_string(input_file_name).unwrap().lines());
+        fn solve(input: &str) -> String {
+            let (size, points) = parse_input(input.lines());
             let antinodes = get_all_antinodes(&points, &size);
             antinodes.len().to_string()
         }
@@ -1,324 +1,221 @@
-use std::usize;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt;
 
-fn parse_input_line(line: &str) -> Vec<Option<i32>> {
-    let mut result = Vec::with_capacity(line.len());
-    for (idx, c) in line.chars().enumerate() {
-        let value_to_add = if idx % 2 == 0 { Some((idx / 2) as i32) } else { None };
-        for _ in 0..c.to_digit(10).unwrap() {
-            result.push(value_to_add);
-        }
-    }
-    result
-}
-
-fn compact(disk_line: &mut Vec<Option<i32>>) {
-    let mut lp = 0;
-    let mut rp = disk_line.len() - 1;
+// Disk map digits are single decimal digits, so are the span lengths
+const MAX_SPAN_LEN: usize = 9;
 
-    while lp < rp {
-        if disk_line[lp].is_some() {
-            lp += 1;
-        } else if disk_line[rp].is_none() {
-            rp -= 1;
-        } else {
-            disk_line.swap(lp, rp);
-            lp += 1;
-            rp -= 1;
-        }
-    }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Span {
+    start: usize,
+    len: usize,
 }
 
-fn rightmost_range(disk_line: &Vec<Option<i32>>, start_from: usize) -> Option<(i32, i32)> {
-    if start_from >= disk_line.len() {
-        return None;
+impl Span {
+    fn end(&self) -> usize {
+        self.start + self.len
     }
+}
 
-    let mut rp = start_from as i32;
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Disk {
+    // pieces of every file indexed by the file id, only fragmentation
+    // splits a file into several pieces
+    files: Vec<Vec<Span>>,
+    // free spans in the order of the position
+    gaps: Vec<Span>,
+    size: usize,
+}
 
-    while disk_line[rp as usize].is_none() {
-        rp -= 1;
-        if rp < 0 {
-            return None;
+fn parse_input_line(line: &str) -> Disk {
+    let mut disk = Disk {
+        files: Vec::with_capacity(line.len() / 2 + 1),
+        gaps: Vec::with_capacity(line.len() / 2),
+        size: 0,
+    };
+    for (idx, len) in aoc::input::digits(line).enumerate() {
+        let span = Span {
+            start: disk.size,
+            len: len as usize,
+        };
+        if idx % 2 == 0 {
+            disk.files.push(vec![span]);
+        } else if span.len > 0 {
+            disk.gaps.push(span);
         }
+        disk.size += span.len;
     }
+    disk
+}
 
-    let mut lp = rp;
-    while lp >= 0 && disk_line[lp as usize] == disk_line[rp as usize] {
-        lp -= 1;
+// Moves blocks one by one from the end of the disk to the leftmost free space
+fn compact(disk: &mut Disk) {
+    let mut gaps = disk.gaps.iter().copied().peekable();
+    let mut current_gap = gaps.next();
+    for pieces in disk.files.iter_mut().rev() {
+        let file = pieces[0];
+        if file.len == 0 {
+            continue;
+        }
+        let mut remaining = file.len;
+        let mut moved = Vec::new();
+        while remaining > 0 {
+            let Some(gap) = current_gap.as_mut().filter(|gap| gap.start < file.start) else {
+                break;
+            };
+            let count = remaining.min(gap.len);
+            moved.push(Span {
+                start: gap.start,
+                len: count,
+            });
+            remaining -= count;
+            gap.start += count;
+            gap.len -= count;
+            if gap.len == 0 {
+                current_gap = gaps.next();
+            }
+        }
+        if remaining == file.len {
+            // gaps to the left of this file are all used, so are they for the rest
+            break;
+        }
+        if remaining > 0 {
+            moved.push(Span {
+                start: file.start,
+                len: remaining,
+            });
+        }
+        *pieces = moved;
     }
-
-    Some((lp + 1, rp))
+    disk.gaps = disk.free_spans();
 }
 
-fn leftmost_empty_range(disk_line: &Vec<Option<i32>>, start_from: usize, size: usize) -> Option<(i32, i32)> {
-    let mut lp = start_from as i32;
-    while disk_line[lp as usize].is_some() {
-        lp += 1;
-        if lp >= disk_line.len() as i32 {
-            return None;
-        }
+// Moves whole files, starting from the last one, to the leftmost gap that
+// fits them. Gap starts are kept in a min-heap per gap length, so finding
+// the leftmost fitting gap takes a look at MAX_SPAN_LEN heaps
+fn compact_no_fragmentation(disk: &mut Disk) {
+    let mut free: Vec<BinaryHeap<Reverse<usize>>> = vec![BinaryHeap::new(); MAX_SPAN_LEN + 1];
+    for gap in disk.gaps.iter() {
+        free[gap.len].push(Reverse(gap.start));
     }
 
-    let mut rp = lp;
-    for _ in 0..size {
-        if disk_line[lp as usize] != disk_line[rp as usize] {
-            return leftmost_empty_range(disk_line, rp as usize, size);
+    for (id, pieces) in disk.files.iter_mut().enumerate().rev() {
+        let file = pieces[0];
+        let best = (file.len..=MAX_SPAN_LEN)
+            .filter_map(|len| free[len].peek().map(|Reverse(start)| (*start, len)))
+            .min();
+        let Some((start, gap_len)) = best else {
+            continue;
+        };
+        if start >= file.start {
+            continue;
         }
-        rp += 1;
-        if rp >= disk_line.len() as i32 {
-            return None;
+        free[gap_len].pop();
+        if gap_len > file.len {
+            free[gap_len - file.len].push(Reverse(start + file.len));
         }
+        let to = Span { start, len: file.len };
+        aoc::trace!("moving file {} from {:?} to {:?}", id, file, to);
+        pieces[0] = to;
     }
-
-    Some((lp, rp - 1))
+    disk.gaps = disk.free_spans();
 }
 
-fn swap_ranges(disk_line: &mut Vec<Option<i32>>, from: usize, to: usize, count: usize) {
-    for i in 0..count {
-        disk_line.swap(from + i, to + i);
+impl Disk {
+    fn free_spans(&self) -> Vec<Span> {
+        let mut used: Vec<Span> = self.files.iter().flatten().filter(|s| s.len > 0).copied().collect();
+        used.sort_by_key(|s| s.start);
+        let mut result = Vec::new();
+        let mut pos = 0;
+        for span in used.iter().chain(std::iter::once(&Span {
+            start: self.size,
+            len: 0,
+        })) {
+            if span.start > pos {
+                result.push(Span {
+                    start: pos,
+                    len: span.start - pos,
+                });
+            }
+            pos = span.end();
+        }
+        result
     }
 }
 
-fn compact_no_fragmentation(disk_line: &mut Vec<Option<i32>>) {
-    let mut lp = 0;
-    let mut rp = disk_line.len() - 1;
-    loop {
-        let from = rightmost_range(disk_line, rp);
-        if from.is_none() {
-            return;
-        }
-        let from = from.unwrap();
-        let to = leftmost_empty_range(disk_line, lp, (from.1 - from.0 + 1) as usize);
-        if to.is_some() {
-            let to = to.unwrap();
-            if from.0 > to.0 {
-                swap_ranges(disk_line, from.0 as usize, to.0 as usize, (to.1 - to.0 + 1) as usize);
+// Blocks as in the puzzle description: the last digit of the file id
+// for file blocks, '.' for free ones
+impl fmt::Display for Disk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut blocks = vec!['.'; self.size];
+        for (id, pieces) in self.files.iter().enumerate() {
+            let ch = char::from_digit((id % 10) as u32, 10).unwrap();
+            for span in pieces {
+                blocks[span.start..span.end()].fill(ch);
             }
         }
-
-        lp = 0;
-        rp = (from.0 - 1) as usize;
+        write!(f, "{}", blocks.into_iter().collect::<String>())
     }
 }
 
-fn get_checksum(disk_line: &Vec<Option<i32>>) -> u64 {
+fn get_checksum(disk: &Disk) -> u64 {
     let mut result = 0;
-
-    for (idx, maybe_value) in disk_line.iter().enumerate() {
-        if maybe_value.is_none() {
-            continue;
+    for (id, pieces) in disk.files.iter().enumerate() {
+        for span in pieces {
+            // sum of start..end
+            let positions = (span.start + span.end()).saturating_sub(1) * span.len / 2;
+            result += (id * positions) as u64;
         }
-        result += idx as u64 * maybe_value.unwrap() as u64;
     }
-
     result
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day_09::{get_checksum, leftmost_empty_range};
-
-    use super::{compact, compact_no_fragmentation, parse_input_line, rightmost_range};
+    use super::{compact, compact_no_fragmentation, get_checksum, parse_input_line, Span};
 
     #[test]
     fn parse_input_line_test() {
-        let input = "12345";
-        let disk_line = parse_input_line(input);
-        assert_eq!(
-            disk_line,
-            vec![
-                Some(0),
-                None,
-                None,
-                Some(1),
-                Some(1),
-                Some(1),
-                None,
-                None,
-                None,
-                None,
-                Some(2),
-                Some(2),
-                Some(2),
-                Some(2),
-                Some(2)
-            ]
-        );
-
-        let input = "2333133121414131402";
-        let disk_line = parse_input_line(input);
-        assert_eq!(
-            disk_line,
-            vec![
-                Some(0),
-                Some(0),
-                None,
-                None,
-                None,
-                Some(1),
-                Some(1),
-                Some(1),
-                None,
-                None,
-                None,
-                Some(2),
-                None,
-                None,
-                None,
-                Some(3),
-                Some(3),
-                Some(3),
-                None,
-                Some(4),
-                Some(4),
-                None,
-                Some(5),
-                Some(5),
-                Some(5),
-                Some(5),
-                None,
-                Some(6),
-                Some(6),
-                Some(6),
-                Some(6),
-                None,
-                Some(7),
-                Some(7),
-                Some(7),
-                None,
-                Some(8),
-                Some(8),
-                Some(8),
-                Some(8),
-                Some(9),
-                Some(9),
-            ]
-        );
+        let disk = parse_input_line("12345");
+        assert_eq!(disk.to_string(), "0..111....22222");
+        assert_eq!(disk.files[1], vec![Span { start: 3, len: 3 }]);
+        assert_eq!(disk.gaps, vec![Span { start: 1, len: 2 }, Span { start: 6, len: 4 }]);
+
+        let disk = parse_input_line("2333133121414131402");
+        assert_eq!(disk.to_string(), "00...111...2...333.44.5555.6666.777.888899");
+        assert_eq!(disk.gaps.len(), 8);
     }
 
     #[test]
     fn compact_test() {
-        let line = "12345";
-        let mut disk_line = parse_input_line(line);
-        compact(&mut disk_line);
-        assert_eq!(
-            disk_line,
-            vec![
-                Some(0),
-                Some(2),
-                Some(2),
-                Some(1),
-                Some(1),
-                Some(1),
-                Some(2),
-                Some(2),
-                Some(2),
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-            ]
-        );
-    }
-
-    #[test]
-    fn rightmost_range_test() {
-        let input = "2333133121414131402";
-        let disk_line = parse_input_line(input);
-
-        let range = rightmost_range(&disk_line, 41);
-        assert_eq!(range, Some((40, 41)));
-
-        let range = rightmost_range(&disk_line, 39);
-        assert_eq!(range, Some((36, 39)));
-
-        let range = rightmost_range(&disk_line, 35);
-        assert_eq!(range, Some((32, 34)));
-
-        let range = rightmost_range(&disk_line, 9);
-        assert_eq!(range, Some((5, 7)));
-
-        let range = rightmost_range(&disk_line, 2);
-        assert_eq!(range, Some((0, 1)));
-    }
-
-    #[test]
-    fn leftmost_empty_range_test() {
-        let input = "2333133121414131402";
-        let disk_line = parse_input_line(input);
-
-        let range = leftmost_empty_range(&disk_line, 0, 2);
-        assert_eq!(range, Some((2, 3)));
-
-        let range = leftmost_empty_range(&disk_line, 0, 3);
-        assert_eq!(range, Some((2, 4)));
-
-        let range = leftmost_empty_range(&disk_line, 0, 4);
-        assert_eq!(range, None);
+        let mut disk = parse_input_line("12345");
+        compact(&mut disk);
+        assert_eq!(disk.to_string(), "022111222......");
+        assert_eq!(disk.gaps, vec![Span { start: 9, len: 6 }]);
+
+        let mut disk = parse_input_line("2333133121414131402");
+        compact(&mut disk);
+        assert_eq!(disk.to_string(), "0099811188827773336446555566..............");
+
+        // an empty last file doesn't stop the files before it from moving
+        let mut disk = parse_input_line("13200");
+        compact(&mut disk);
+        assert_eq!(disk.to_string(), "011...");
     }
 
     #[test]
     fn compact_no_fragmentation_test() {
-        let input = "2333133121414131402";
-        let mut disk_line = parse_input_line(input);
-        compact_no_fragmentation(&mut disk_line);
-        assert_eq!(
-            disk_line,
-            vec![
-                Some(0),
-                Some(0),
-                Some(9),
-                Some(9),
-                Some(2),
-                Some(1),
-                Some(1),
-                Some(1),
-                Some(7),
-                Some(7),
-                Some(7),
-                None,
-                Some(4),
-                Some(4),
-                None,
-                Some(3),
-                Some(3),
-                Some(3),
-                None,
-                None,
-                None,
-                None,
-                Some(5),
-                Some(5),
-                Some(5),
-                Some(5),
-                None,
-                Some(6),
-                Some(6),
-                Some(6),
-                Some(6),
-                None,
-                None,
-                None,
-                None,
-                None,
-                Some(8),
-                Some(8),
-                Some(8),
-                Some(8),
-                None,
-                None,
-            ]
-        )
+        let mut disk = parse_input_line("2333133121414131402");
+        compact_no_fragmentation(&mut disk);
+        assert_eq!(disk.to_string(), "00992111777.44.333....5555.6666.....8888..");
+        assert_eq!(get_checksum(&disk), 2858);
     }
 
     #[test]
     fn get_checksum_test() {
-        let line = "2333133121414131402";
-        let mut disk_line = parse_input_line(line);
-        compact(&mut disk_line);
-        assert_eq!(get_checksum(&disk_line), 1928);
+        let mut disk = parse_input_line("2333133121414131402");
+        compact(&mut disk);
+        assert_eq!(get_checksum(&disk), 1928);
     }
 }
 
@@ -327,12 +224,12 @@ pub mod part1 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(input_file_name: &str) -> String {
+        fn solve(input: &str) -> String {
             let mut result = 0;
-            for line in std::fs::read_to_string(input_file_name).unwrap().lines() {
-                let mut disk_line = parse_input_line(line);
-                compact(&mut disk_line);
-                result += get_checksum(&disk_line);
+            for line in input.lines() {
+                let mut disk = parse_input_line(line);
+                compact(&mut disk);
+                result += get_checksum(&disk);
             }
             result.to_string()
         }
@@ -362,12 +259,12 @@ pub mod part2 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(input_file_name: &str) -> String {
+        fn solve(input: &str) -> String {
             let mut result = 0;
-            for line in std::fs::read_to_string(input_file_name).unwrap().lines() {
-                let mut disk_line = parse_input_line(line);
-                compact_no_fragmentation(&mut disk_line);
-                result += get_checksum(&disk_line);
+            for line in input.lines() {
+                let mut disk = parse_input_line(line);
+                compact_no_fragmentation(&mut disk);
+                result += get_checksum(&disk);
             }
             result.to_string()
         }
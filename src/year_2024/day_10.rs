@@ -1,5 +1,3 @@
-use std::collections::{HashSet, VecDeque};
-
 type Point = aoc::Point<i32>;
 
 #[derive(Default, Debug, Clone)]
@@ -46,53 +44,37 @@ where
     field
 }
 
-// How many 9's we can approach from the specified 0
-fn get_hiking_score(field: &Field, start: &Point, skip_visited: bool) -> i32 {
-    let mut result = 0;
-
-    let mut queue: VecDeque<Point> = VecDeque::default();
-    queue.push_back(Point {
-        row: start.row,
-        col: start.col,
-    });
-
-    let mut visited: HashSet<Point> = HashSet::default();
-    visited.insert(Point {
-        row: start.row,
-        col: start.col,
-    });
-
-    while !queue.is_empty() {
-        let point = queue.pop_front().unwrap();
-        let height = field.nodes[point.row as usize][point.col as usize];
-
-        if height == 9 {
-            result += 1;
-            continue;
-        }
-
-        let neighbors = get_neighbors(&point);
-        for n in neighbors.into_iter() {
-            if n.row < 0 || n.col < 0 || n.row >= field.nodes.len() as i32 || n.col >= field.nodes[0].len() as i32 {
-                continue;
-            }
-
-            if field.nodes[n.row as usize][n.col as usize] != height + 1 {
-                continue;
-            }
+fn get_height(field: &Field, point: &Point) -> Option<i32> {
+    if point.row < 0 || point.col < 0 {
+        return None;
+    }
+    field.nodes.get(point.row as usize)?.get(point.col as usize).copied()
+}
 
-            if skip_visited {
-                if visited.contains(&n) {
-                    continue;
-                }
-                visited.insert(n.clone());
-            }
+// Points one step higher than the given one
+fn get_uphill_neighbors(field: &Field, point: &Point) -> Vec<Point> {
+    let height = get_height(field, point).unwrap();
+    get_neighbors(point)
+        .into_iter()
+        .filter(|n| get_height(field, n) == Some(height + 1))
+        .collect()
+}
 
-            queue.push_back(n);
-        }
-    }
+// How many 9's we can approach from the specified 0
+fn get_hiking_score(field: &Field, start: &Point) -> i32 {
+    aoc::search::reachable([*start], |p| get_uphill_neighbors(field, p))
+        .iter()
+        .filter(|p| get_height(field, p) == Some(9))
+        .count() as i32
+}
 
-    result
+// How many distinct trails lead from the specified 0 to any 9
+fn get_hiking_rating(field: &Field, start: &Point) -> i32 {
+    aoc::search::count_paths(
+        [*start],
+        |p| get_uphill_neighbors(field, p),
+        |p| get_height(field, p) == Some(9),
+    ) as i32
 }
 
 fn get_hiking_paths_score(field: &Field, skip_visited: bool) -> i32 {
@@ -102,14 +84,15 @@ fn get_hiking_paths_score(field: &Field, skip_visited: bool) -> i32 {
             if *value != 0 {
                 continue;
             }
-            result += get_hiking_score(
-                field,
-                &Point {
-                    row: row_idx as i32,
-                    col: col_idx as i32,
-                },
-                skip_visited,
-            );
+            let start = Point {
+                row: row_idx as i32,
+                col: col_idx as i32,
+            };
+            result += if skip_visited {
+                get_hiking_score(field, &start)
+            } else {
+                get_hiking_rating(field, &start)
+            };
         }
     }
 
@@ -157,8 +140,8 @@ pub mod part1 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(input_file_name: &str) -> String {
-            let field = parse_field(std::fs::read_to_string(input_file_name).unwrap().lines());
+        fn solve(input: &str) -> String {
+            let field = parse_field(input.lines());
             get_hiking_paths_score(&field, true).to_string()
         }
 
@@ -187,8 +170,8 @@ pub mod part2 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(input_file_name: &str) -> String {
-            let field = parse_field(std::fs::read_to_string(input_file_name).unwrap().lines());
+        fn solve(input: &str) -> String {
+            let field = parse_field(input.lines());
             get_hiking_paths_score(&field, false).to_string()
         }
 
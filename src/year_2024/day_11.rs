@@ -1,18 +1,10 @@
-use std::collections::HashMap;
+use aoc::memo::Memo;
 
-type Stones = HashMap<u64, u64>;
-
-fn parse_input(line: &str) -> Stones {
-    let mut result = HashMap::new();
-    for value in line
-        .split(' ')
+fn parse_input(line: &str) -> Vec<u64> {
+    line.split(' ')
         .filter(|i| !i.is_empty())
         .map(|s| aoc::parse_or_panic::<u64>(s))
-    {
-        *result.entry(value).or_default() += 1;
-    }
-
-    result
+        .collect()
 }
 
 fn get_number_of_digits(stone: u64) -> i32 {
@@ -37,36 +29,39 @@ fn split_stone(stone: u64) -> (u64, u64) {
     )
 }
 
-fn count_stones(stones: &Stones) -> u64 {
-    let mut result = 0;
-    for (_, count) in stones.iter() {
-        result += count;
+fn blink(stone: u64) -> Vec<u64> {
+    if stone == 0 {
+        vec![1]
+    } else if get_number_of_digits(stone) % 2 == 0 {
+        let (s1, s2) = split_stone(stone);
+        vec![s1, s2]
+    } else {
+        vec![stone * 2024]
     }
-    result
 }
 
-fn blink(stones: &Stones) -> Stones {
-    let mut result = Stones::new();
-
-    for (stone, count) in stones.iter() {
-        if *stone == 0 {
-            *result.entry(1).or_default() += count;
-        } else if get_number_of_digits(*stone) % 2 == 0 {
-            let (s1, s2) = split_stone(*stone);
-
-            *result.entry(s1).or_default() += count;
-            *result.entry(s2).or_default() += count;
-        } else {
-            *result.entry(*stone * 2024).or_default() += count
-        }
+// Stones don't affect each other, so the number of stones a single stone
+// turns into only depends on the stone itself and the number of blinks left
+fn count_stones(stone: u64, blinks: usize, memo: &mut Memo<(u64, usize), u64>) -> u64 {
+    if blinks == 0 {
+        return 1;
     }
+    memo.get_or_compute(&(stone, blinks), |memo| {
+        blink(stone)
+            .into_iter()
+            .map(|s| count_stones(s, blinks - 1, memo))
+            .sum()
+    })
+}
 
-    result
+fn count_all_stones(stones: &[u64], blinks: usize) -> u64 {
+    let mut memo = Memo::new();
+    stones.iter().map(|&s| count_stones(s, blinks, &mut memo)).sum()
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day_11::{count_stones, get_number_of_digits, split_stone};
+    use crate::day_11::{count_all_stones, get_number_of_digits, split_stone};
 
     use super::{blink, parse_input};
 
@@ -88,7 +83,7 @@ mod tests {
     #[test]
     fn blink_test() {
         let stones = parse_input("0 1 10 99 999");
-        let stones = blink(&stones);
+        let stones: Vec<u64> = stones.into_iter().flat_map(blink).collect();
 
         assert_eq!(stones, parse_input("1 2024 1 0 9 9 2021976"))
     }
@@ -97,7 +92,7 @@ mod tests {
     fn blink_test_longer() {
         let mut stones = parse_input("125 17");
         for _ in 0..6 {
-            stones = blink(&stones);
+            stones = stones.into_iter().flat_map(blink).collect();
         }
         assert_eq!(
             stones,
@@ -107,26 +102,21 @@ mod tests {
 
     #[test]
     fn blink_test_25() {
-        let mut stones = parse_input("125 17");
-        for _ in 0..25 {
-            stones = blink(&stones);
-        }
-        assert_eq!(count_stones(&stones), 55312);
+        let stones = parse_input("125 17");
+        assert_eq!(count_all_stones(&stones, 6), 22);
+        assert_eq!(count_all_stones(&stones, 25), 55312);
     }
 }
 
 pub mod part1 {
-    use super::{blink, count_stones, parse_input};
+    use super::{count_all_stones, parse_input};
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(input_file_name: &str) -> String {
-            for line in std::fs::read_to_string(input_file_name).unwrap().lines() {
-                let mut stones = parse_input(line);
-                for _ in 0..25 {
-                    stones = blink(&stones);
-                }
-                return count_stones(&stones).to_string();
+        fn solve(input: &str) -> String {
+            for line in input.lines() {
+                let stones = parse_input(line);
+                return count_all_stones(&stones, 25).to_string();
             }
             unreachable!()
         }
@@ -146,17 +136,14 @@ pub mod part1 {
 }
 
 pub mod part2 {
-    use super::{blink, count_stones, parse_input};
+    use super::{count_all_stones, parse_input};
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(input_file_name: &str) -> String {
-            for line in std::fs::read_to_string(input_file_name).unwrap().lines() {
-                let mut stones = parse_input(line);
-                for _ in 0..75 {
-                    stones = blink(&stones);
-                }
-                return count_stones(&stones).to_string();
+        fn solve(input: &str) -> String {
+            for line in input.lines() {
+                let stones = parse_input(line);
+                return count_all_stones(&stones, 75).to_string();
             }
             unreachable!()
         }
@@ -11,66 +11,39 @@ struct Machine {
     ty: i64,
 }
 
-use regex::Regex;
-
-fn parse_input<I, S>(lines: I) -> Vec<Machine>
-where
-    I: Iterator<Item = S>,
-    S: AsRef<str>,
-{
-    let mut result = Vec::new();
-    let mut m = Machine::default();
-
-    let button_a_re = Regex::new(r"^Button A: X[+=](\d+), Y[+=](\d+)$").unwrap();
-    let button_b_re = Regex::new(r"^Button B: X[+=](\d+), Y[+=](\d+)$").unwrap();
-    let prize_re = Regex::new(r"^Prize: X=(\d+), Y=(\d+)$").unwrap();
-
-    for line in lines {
-        let line = line.as_ref().trim();
-        if let Some(captures) = button_a_re.captures(line) {
-            m.ax = aoc::parse_or_panic(&captures[1]);
-            m.ay = aoc::parse_or_panic(&captures[2]);
-        } else if let Some(captures) = button_b_re.captures(line) {
-            m.bx = aoc::parse_or_panic(&captures[1]);
-            m.by = aoc::parse_or_panic(&captures[2]);
-        } else if let Some(captures) = prize_re.captures(line) {
-            m.tx = aoc::parse_or_panic(&captures[1]);
-            m.ty = aoc::parse_or_panic(&captures[2]);
-        } else if line.is_empty() {
-            result.push(m);
-        }
+fn parse_machine(block: &str) -> aoc::parse::Result<Machine> {
+    let lines: Vec<&str> = block.lines().map(|l| l.trim()).collect();
+    if lines.len() != 3 {
+        return Err(aoc::parse::ParseError::new(1, "expected 3 lines per machine"));
     }
-    result.push(m);
+    let (ax, ay) = aoc::scan!(lines[0], "Button A: X+{}, Y+{}", i32, i32).map_err(|e| e.at_line(1))?;
+    let (bx, by) = aoc::scan!(lines[1], "Button B: X+{}, Y+{}", i32, i32).map_err(|e| e.at_line(2))?;
+    let (tx, ty) = aoc::scan!(lines[2], "Prize: X={}, Y={}", i64, i64).map_err(|e| e.at_line(3))?;
+    Ok(Machine { ax, ay, bx, by, tx, ty })
+}
 
-    result
+fn parse_input(text: &str) -> Vec<Machine> {
+    aoc::input::paragraphs(text)
+        .map(|block| parse_machine(block).unwrap_or_else(|e| panic!("{}", e)))
+        .collect()
 }
 
 fn solve(machine: &Machine, part2: bool) -> Option<(i64, i64)> {
-    let det = (machine.ax * machine.by - machine.ay * machine.bx) as i64;
-
-    if det == 0 {
-        return None;
-    }
     let tx = if part2 { machine.tx + 10000000000000 } else { machine.tx };
     let ty = if part2 { machine.ty + 10000000000000 } else { machine.ty };
-    let a_numerator = machine.by as i64 * tx - machine.bx as i64 * ty;
-    let b_numerator = -machine.ay as i64 * tx + machine.ax as i64 * ty;
-
-    if a_numerator % det != 0 || b_numerator % det != 0 {
-        return None;
-    }
-
-    let a = a_numerator / det;
-    let b = b_numerator / det;
-
-    Some((a, b))
+    let matrix = [
+        vec![machine.ax as i64, machine.bx as i64],
+        vec![machine.ay as i64, machine.by as i64],
+    ];
+    let pushes = aoc::math::solve_linear_system(&matrix, &[tx, ty])?;
+    Some((pushes[0], pushes[1]))
 }
 
 #[cfg(test)]
 mod tests {
     use crate::day_13::{solve, Machine};
 
-    use super::parse_input;
+    use super::{parse_input, parse_machine};
 
     #[test]
     fn solve_test() {
@@ -128,26 +101,31 @@ mod tests {
         assert!(solve(&machine, true).is_some());
     }
 
+    #[test]
+    fn parse_machine_error_test() {
+        let err = parse_machine("Button A: X+94, Y+34\nButton B: X+22, Y+6x\nPrize: X=8400, Y=5400").unwrap_err();
+        assert_eq!((err.line, err.col), (2, 19));
+    }
+
     #[test]
     fn parse_input_test() {
-        let lines = vec![
-            "Button A: X+94, Y+34",
-            "Button B: X+22, Y+67",
-            "Prize: X=8400, Y=5400",
-            "",
-            "Button A: X+26, Y+66",
-            "Button B: X+67, Y+21",
-            "Prize: X=12748, Y=12176",
-            "",
-            "Button A: X+17, Y+86",
-            "Button B: X+84, Y+37",
-            "Prize: X=7870, Y=6450",
-            "",
-            "Button A: X+69, Y+23",
-            "Button B: X+27, Y+71",
-            "Prize: X=18641, Y=10279",
-        ];
-        let machines = parse_input(lines.iter());
+        let text = "Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279
+";
+        let machines = parse_input(text);
 
         let expected = vec![
             Machine {
@@ -184,9 +162,7 @@ mod tests {
             },
         ];
 
-        for (actual, expected) in machines.iter().zip(expected.iter()) {
-            assert_eq!(actual, expected);
-        }
+        assert_eq!(machines, expected);
     }
 }
 
@@ -195,8 +171,8 @@ pub mod part1 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(input_file_name: &str) -> String {
-            let machines = parse_input(std::fs::read_to_string(input_file_name).unwrap().lines());
+        fn solve(input: &str) -> String {
+            let machines = parse_input(input);
             let mut result = 0;
             for machine in machines.iter() {
                 if let Some(pushes) = solve(&machine, false) {
@@ -225,8 +201,8 @@ pub mod part2 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(input_file_name: &str) -> String {
-            let machines = parse_input(std::fs::read_to_string(input_file_name).unwrap().lines());
+        fn solve(input: &str) -> String {
+            let machines = parse_input(input);
             let mut result = 0;
             for machine in machines.iter() {
                 if let Some(pushes) = solve(&machine, true) {
@@ -205,8 +205,8 @@ pub mod part1 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(input_file_name: &str) -> String {
-            let mut robots = parse_robots(std::fs::read_to_string(input_file_name).unwrap().lines());
+        fn solve(input: &str) -> String {
+            let mut robots = parse_robots(input.lines());
             let field_size = Point { row: 103, col: 101 };
             let count = solve(&mut robots, 100, &field_size);
             (count.0 * count.1 * count.2 * count.3).to_string()
@@ -243,20 +243,43 @@ pub mod part1 {
 pub mod part2 {
     use std::i32;
 
+    use aoc::image::{Image, BLACK};
+    use aoc::viz::{Cell, Color, Frame};
+
     use crate::day_14::{calculate_position_variance, move_robot, parse_robots, Point};
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(input_file_name: &str) -> String {
-            let mut robots = parse_robots(std::fs::read_to_string(input_file_name).unwrap().lines());
+        fn solve(input: &str) -> String {
+            let mut robots = parse_robots(input.lines());
             let field_size = Point { row: 103, col: 101 };
+            let visualize = aoc::viz::is_enabled();
             for i in 0..10000 {
                 for j in 0..robots.len() {
                     move_robot(&mut robots[j], &field_size);
                 }
+                if visualize {
+                    aoc::viz::show(&Frame::from_points(
+                        field_size.row as usize,
+                        field_size.col as usize,
+                        robots.iter().map(|r| r.pos),
+                        Cell::new('#').fg(Color::Green),
+                        Cell::new('.'),
+                    ));
+                }
                 let var = calculate_position_variance(&robots);
                 // println!("{:.2} {:.2}", var.0, var.1);
                 if var.0 < 500.0 && var.1 < 500.0 {
+                    if aoc::image::is_enabled() {
+                        let tree = Image::from_points(
+                            field_size.row as usize,
+                            field_size.col as usize,
+                            robots.iter().map(|r| r.pos),
+                            [0, 200, 0],
+                            BLACK,
+                        );
+                        aoc::image::save("2024_day_14_tree", &tree.scaled(4)).expect("Failed to save the tree");
+                    }
                     return (i + 1).to_string();
                 }
             }
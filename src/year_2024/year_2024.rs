@@ -36,8 +36,7 @@ impl aoc::Solver for Solver {
         aoc::solve::<day_05::part1::Puzzle>(day, part);
         aoc::solve::<day_05::part2::Puzzle>(day, part);
 
-        aoc::solve::<day_06::part1::Puzzle>(day, part);
-        aoc::solve::<day_06::part2::Puzzle>(day, part);
+        aoc::solve_day::<day_06::Solution>(day, part);
 
         aoc::solve::<day_07::part1::Puzzle>(day, part);
         aoc::solve::<day_07::part2::Puzzle>(day, part);
@@ -64,3 +63,11 @@ impl aoc::Solver for Solver {
         aoc::solve::<day_14::part2::Puzzle>(day, part);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn no_raw_file_io_test() {
+        aoc::testing::assert_no_raw_file_io(env!("CARGO_MANIFEST_DIR"));
+    }
+}
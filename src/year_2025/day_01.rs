@@ -7,13 +7,13 @@ enum Direction {
 #[derive(Debug, Clone, Copy)]
 struct Rotation {
     direction: Direction,
-    distance: i32,
+    distance: i64,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct RotationResult {
     pos: i32,
-    zero_count: u32,
+    zero_count: u64,
 }
 
 fn parse_line(line: &str) -> Rotation {
@@ -22,34 +22,24 @@ fn parse_line(line: &str) -> Rotation {
         Some('R') => Direction::Right,
         _ => unreachable!(),
     };
-    let dist = aoc::parse_or_panic::<i32>(&line[1..]);
+    let dist = aoc::parse_or_panic::<i64>(&line[1..]);
     Rotation {
         direction: dir,
         distance: dist,
     }
 }
 
+// The dial has positions from 0 to max
 fn rotate(pos: i32, rot: Rotation, max: i32) -> RotationResult {
-    let mut pos = pos;
-    let mut zero_count = 0;
     let delta = match rot.direction {
-        Direction::Left => -1,
-        Direction::Right => 1,
+        Direction::Left => -(rot.distance as i128),
+        Direction::Right => rot.distance as i128,
     };
-    for _ in 0..rot.distance {
-        pos += delta;
-        if pos > 99 {
-            pos -= 100;
-        }
-        if pos < 0 {
-            pos += 100;
-        }
-        if pos == 0 {
-            zero_count += 1;
-        }
+    let (pos, zero_count) = aoc::math::dial_turn(pos as i128, delta, max as i128 + 1);
+    RotationResult {
+        pos: pos as i32,
+        zero_count: u64::try_from(zero_count).expect("Negative zero count"),
     }
-
-    RotationResult { pos, zero_count }
 }
 
 #[cfg(test)]
@@ -103,12 +93,12 @@ mod tests {
 pub mod part1 {
     use crate::day_01::{parse_line, rotate, Rotation};
 
-    fn solve(input: &[Rotation]) -> u32 {
+    fn solve(input: &[Rotation]) -> u64 {
         let mut count = 0;
         let mut pos = 50;
         let max = 99;
         for rotation in input.iter() {
-            let result = rotate(pos, rotation.clone(), 99);
+            let result = rotate(pos, *rotation, max);
             pos = result.pos;
             if pos == 0 {
                 count += 1;
@@ -119,13 +109,13 @@ pub mod part1 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
-            let mut input = Vec::new();
-            for line in std::fs::read_to_string(file_name).unwrap().lines() {
-                input.push(parse_line(&line));
+        fn solve(input: &str) -> String {
+            let mut rotations = Vec::new();
+            for line in input.lines() {
+                rotations.push(parse_line(line));
             }
 
-            let result = solve(&input);
+            let result = solve(&rotations);
             result.to_string()
         }
 
@@ -173,17 +163,36 @@ pub mod part1 {
             assert_eq!(result.pos, 50);
             assert_eq!(result.zero_count, 10);
         }
+
+        #[test]
+        fn test_other_dial_size() {
+            let rot = Rotation {
+                direction: Direction::Left,
+                distance: 2_000_000_000,
+            };
+            let result = rotate(3, rot, 9);
+            assert_eq!(result.pos, 3);
+            assert_eq!(result.zero_count, 200_000_000);
+        }
+
+        #[test]
+        fn test_long_distance() {
+            let rot = parse_line("R900000000000000000");
+            let result = rotate(50, rot, 99);
+            assert_eq!(result.pos, 50);
+            assert_eq!(result.zero_count, 9_000_000_000_000_000);
+        }
     }
 }
 pub mod part2 {
     use crate::day_01::{parse_line, rotate, Rotation};
 
-    fn solve(input: &[Rotation]) -> u32 {
+    fn solve(input: &[Rotation]) -> u64 {
         let mut count = 0;
         let mut pos = 50;
         let max = 99;
         for rotation in input.iter() {
-            let result = rotate(pos, rotation.clone(), 99);
+            let result = rotate(pos, *rotation, max);
             pos = result.pos;
             count += result.zero_count;
         }
@@ -192,13 +201,13 @@ pub mod part2 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
-            let mut input = Vec::new();
-            for line in std::fs::read_to_string(file_name).unwrap().lines() {
-                input.push(parse_line(&line));
+        fn solve(input: &str) -> String {
+            let mut rotations = Vec::new();
+            for line in input.lines() {
+                rotations.push(parse_line(line));
             }
 
-            let result = solve(&input);
+            let result = solve(&rotations);
             result.to_string()
         }
 
@@ -1,18 +1,15 @@
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-struct Range {
-    start: i64,
-    end: i64,
-}
+type Range = aoc::interval::Interval<i64>;
 
+// Ranges in the input include both ends
 fn parse_range(s: &str) -> Option<Range> {
     let parts = s.split("-").collect::<Vec<_>>();
     if parts.len() != 2 {
         return None;
     }
-    Some(Range {
-        start: aoc::parse_or_panic::<i64>(parts[0]),
-        end: aoc::parse_or_panic::<i64>(parts[1]),
-    })
+    Some(Range::from_inclusive(
+        aoc::parse_or_panic::<i64>(parts[0]),
+        aoc::parse_or_panic::<i64>(parts[1]),
+    ))
 }
 
 fn parse_input_line(s: &str) -> Vec<Range> {
@@ -30,7 +27,7 @@ mod tests {
 
     #[test]
     fn test_parse_range() {
-        assert_eq!(parse_range("356-567"), Some(Range { start: 356, end: 567 }));
+        assert_eq!(parse_range("356-567"), Some(Range { start: 356, end: 568 }));
         assert_eq!(parse_range("234"), None);
     }
 }
@@ -51,7 +48,7 @@ pub mod part1 {
         let mut result = 0;
 
         for range in input.iter() {
-            for i in range.start..=range.end {
+            for i in range.start..range.end {
                 if let Some((first, second)) = get_parts_from_number(i) {
                     if first == second {
                         result += i;
@@ -65,8 +62,8 @@ pub mod part1 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
-            let mut input = parse_input_line(&std::fs::read_to_string(file_name).unwrap());
+        fn solve(input: &str) -> String {
+            let mut input = parse_input_line(input);
             solve(&input).to_string()
         }
 
@@ -152,7 +149,7 @@ pub mod part2 {
         let mut result = 0;
 
         for range in input.iter() {
-            for i in range.start..=range.end {
+            for i in range.start..range.end {
                 if is_id_invalid(i) {
                     result += i;
                 }
@@ -164,8 +161,8 @@ pub mod part2 {
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
-            let mut input = parse_input_line(&std::fs::read_to_string(file_name).unwrap());
+        fn solve(input: &str) -> String {
+            let mut input = parse_input_line(input);
             solve(&input).to_string()
         }
 
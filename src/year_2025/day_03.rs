@@ -1,38 +1,23 @@
-use std::cmp::Ordering;
-
-fn get_max(slice: &str) -> (usize, i64) {
-    slice
-        .chars()
-        .enumerate()
-        .max_by(|(_, a), (_, b)| if a > b { Ordering::Greater } else { Ordering::Less })
-        .map(|(index, ch)| (index, ch as i64 - 0x30))
-        .expect("Max value not found")
+// The largest number made of the given count of batteries, keeping their order
+fn get_max_joltage(bank: &str, battery_count: usize) -> u64 {
+    let digits: Vec<u8> = aoc::input::digits(bank).collect();
+    let (_, joltage) = aoc::select::max_number(&digits, battery_count);
+    joltage
 }
 
 pub mod part1 {
-    use std::{
-        fs::File,
-        io::{BufRead, BufReader},
-    };
+    use super::get_max_joltage;
 
-    use super::get_max;
-
-    fn get_joltage(input: &str) -> i64 {
-        let (left_idx, left_val) = super::get_max(&input[0..input.len() - 1]);
-        let (_, right_val) = get_max(&input[left_idx + 1..]);
-        left_val * 10 + right_val
+    fn get_joltage(input: &str) -> u64 {
+        get_max_joltage(input, 2)
     }
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
-            let file = File::open(file_name).unwrap();
-            let reader = BufReader::new(file);
-
+        fn solve(input: &str) -> String {
             let mut result = 0;
-            for line in reader.lines() {
-                let line = line.unwrap();
-                result += get_joltage(&line);
+            for line in input.lines() {
+                result += get_joltage(line);
             }
 
             result.to_string()
@@ -68,34 +53,18 @@ pub mod part1 {
 }
 
 pub mod part2 {
-    use std::{
-        fs::File,
-        io::{BufRead, BufReader},
-    };
-
-    use super::get_max;
-
-    fn get_joltage(input: &str) -> i64 {
-        let mut result = 0;
-        let mut start = 0;
-        for end in (0..12).rev() {
-            let (idx, val) = get_max(&input[start..input.len() - end]);
-            result = result * 10 + val;
-            start = idx + start + 1;
-        }
-        result
+    use super::get_max_joltage;
+
+    fn get_joltage(input: &str) -> u64 {
+        get_max_joltage(input, 12)
     }
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
-            let file = File::open(file_name).unwrap();
-            let reader = BufReader::new(file);
-
+        fn solve(input: &str) -> String {
             let mut result = 0;
-            for line in reader.lines() {
-                let line = line.unwrap();
-                result += get_joltage(&line);
+            for line in input.lines() {
+                result += get_joltage(line);
             }
 
             result.to_string()
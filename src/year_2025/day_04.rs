@@ -1,4 +1,4 @@
-use aoc::Point;
+use aoc::automaton::{Automaton, Neighborhood, Neighbors, Rule};
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 enum Node {
@@ -39,54 +39,30 @@ where
     Field::from_flat_vector(nodes, cols.expect("Col count not found"))
 }
 
-fn count_neighbors(field: &Field, point: Point<i32>) -> i32 {
-    let mut result = 0;
-    for n in point.neighbors_all() {
-        if !field.is_inside(n.row, n.col) {
-            continue;
-        }
-        if *field.get(n.row as usize, n.col as usize) == Node::Roll {
-            result += 1;
-        }
+// A roll can be taken away by a forklift if fewer than four rolls surround it
+fn remove_accessible(node: &Node, neighbors: &Neighbors<Node>) -> Node {
+    if *node == Node::Roll && neighbors.count(|n| *n == Node::Roll) < 4 {
+        Node::Empty
+    } else {
+        *node
     }
-    result
 }
 
-pub mod part1 {
-    use std::{
-        fs::File,
-        io::{BufRead, BufReader},
-    };
-
-    use aoc::Point;
-
-    use crate::day_04::{count_neighbors, parse_field, Field, Node};
-
-    fn solve(field: &Field) -> i32 {
-        let mut result = 0;
+fn rolls_automaton(field: &Field) -> Automaton<Node, Rule<Node>> {
+    Automaton::new(field.clone(), Neighborhood::All, remove_accessible)
+}
 
-        for col in 0..field.get_col_count() as i32 {
-            for row in 0..field.get_row_count() as i32 {
-                if *field.get(row as usize, col as usize) == Node::Empty {
-                    continue;
-                }
-                let point = Point { row: row, col: col };
-                if count_neighbors(&field, point) < 4 {
-                    result += 1;
-                }
-            }
-        }
+pub mod part1 {
+    use crate::day_04::{parse_field, rolls_automaton, Field};
 
-        result
+    fn solve(field: &Field) -> usize {
+        rolls_automaton(field).step()
     }
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
-            let file = File::open(file_name).unwrap();
-            let reader = BufReader::new(file);
-            let field =
-                parse_field(std::fs::read_to_string(file_name).unwrap().lines()).expect("Unable to parse field");
+        fn solve(input: &str) -> String {
+            let field = parse_field(input.lines()).expect("Unable to parse field");
             solve(&field).to_string()
         }
 
@@ -137,65 +113,30 @@ pub mod part1 {
 }
 
 pub mod part2 {
-    use std::{
-        fs::File,
-        io::{BufRead, BufReader},
-    };
+    use crate::day_04::{parse_field, rolls_automaton, Field, Node};
 
-    use aoc::Point;
-
-    use crate::day_04::{count_neighbors, parse_field, Field, Node};
-
-    struct MoveResult {
-        field: Field,
-        moved: i32,
-    }
-
-    fn move_rolls(field: &Field) -> MoveResult {
-        let mut result = MoveResult {
-            field: field.clone(),
-            moved: 0,
-        };
-
-        for col in 0..field.get_col_count() as i32 {
-            for row in 0..field.get_row_count() as i32 {
-                if *field.get(row as usize, col as usize) == Node::Empty {
-                    continue;
-                }
-                let point = Point { row: row, col: col };
-                if count_neighbors(&field, point) < 4 {
-                    *result.field.get_mut(row as usize, col as usize) = Node::Empty;
-                    result.moved += 1;
+    fn count_rolls(field: &Field) -> usize {
+        let mut result = 0;
+        for row in 0..field.get_row_count() {
+            for col in 0..field.get_col_count() {
+                if *field.get(row, col) == Node::Roll {
+                    result += 1;
                 }
             }
         }
-
         result
     }
 
-    fn solve(field: &Field) -> i32 {
-        let mut result = 0;
-
-        let mut current_field = field.clone();
-        loop {
-            let move_result = move_rolls(&current_field);
-            if move_result.moved == 0 {
-                break;
-            }
-            result += move_result.moved;
-            current_field = move_result.field;
-        }
-
-        result
+    fn solve(field: &Field) -> usize {
+        let mut automaton = rolls_automaton(field).with_frontier();
+        automaton.run_until_stable();
+        count_rolls(field) - count_rolls(automaton.field())
     }
 
     pub struct Puzzle {}
     impl aoc::Puzzle for Puzzle {
-        fn solve(file_name: &str) -> String {
-            let file = File::open(file_name).unwrap();
-            let reader = BufReader::new(file);
-            let field =
-                parse_field(std::fs::read_to_string(file_name).unwrap().lines()).expect("Unable to parse field");
+        fn solve(input: &str) -> String {
+            let field = parse_field(input.lines()).expect("Unable to parse field");
             solve(&field).to_string()
         }
 
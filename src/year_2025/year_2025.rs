@@ -20,3 +20,11 @@ impl aoc::Solver for Solver {
         aoc::solve::<day_04::part2::Puzzle>(day, part);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn no_raw_file_io_test() {
+        aoc::testing::assert_no_raw_file_io(env!("CARGO_MANIFEST_DIR"));
+    }
+}